@@ -0,0 +1,386 @@
+mod payers;
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+use ethers::providers::{Http, Provider};
+use ethers::types::{Address, Bytes, Transaction, TransactionRequest, TxHash, U256};
+use ethers::utils::{keccak256, rlp};
+use rome_sdk::rome_evm_client::tx::DepositAmount;
+use rome_sdk::rome_solana::payer::SolanaKeyPayer;
+use rome_sdk::{EthSignedTxTuple, RheaTx, Rome, RomeConfig};
+use serde_json::{json, Value};
+
+/// Command-line tool to operate Rome rollups.
+///
+/// Every command prints its result as JSON to stdout, logs are written to stderr.
+#[derive(Parser, Debug)]
+#[command(name = "rome", version)]
+struct Cli {
+    /// Path to the Rome config, JSON or YAML (.yml, .yaml)
+    #[arg(long, short)]
+    config: PathBuf,
+
+    /// Log at the info level
+    #[arg(long, short)]
+    verbose: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Send a raw signed transaction and wait for its confirmation
+    SendRawTx {
+        /// Signed transaction, hex encoded
+        rlp: Bytes,
+    },
+    /// Estimate gas of a transaction
+    EstimateGas {
+        #[arg(long)]
+        chain_id: u64,
+        #[arg(long)]
+        from: Option<Address>,
+        #[arg(long)]
+        to: Option<Address>,
+        /// Call data, hex encoded
+        #[arg(long)]
+        data: Option<Bytes>,
+        /// Value in wei, decimal or 0x-prefixed hex
+        #[arg(long, value_parser = parse_u256)]
+        value: Option<U256>,
+    },
+    /// Balance of an account, in wei
+    Balance {
+        #[arg(long)]
+        chain_id: u64,
+        address: Address,
+    },
+    /// Nonce of an account
+    Nonce {
+        #[arg(long)]
+        chain_id: u64,
+        address: Address,
+    },
+    /// Bytecode of a contract
+    Code {
+        #[arg(long)]
+        chain_id: u64,
+        address: Address,
+    },
+    /// Value of a storage slot of a contract
+    Storage {
+        #[arg(long)]
+        chain_id: u64,
+        address: Address,
+        /// Index of the slot, decimal or 0x-prefixed hex
+        #[arg(value_parser = parse_u256)]
+        slot: U256,
+    },
+    /// Deposit SOLs of the keypair to a rollup account
+    Deposit {
+        #[arg(long)]
+        chain_id: u64,
+        /// Deposit transaction (type 0x7E), hex encoded
+        #[arg(long)]
+        rlp: Bytes,
+        /// Keypair file of the owner of the SOLs
+        #[arg(long)]
+        keypair: PathBuf,
+    },
+    /// Deposit SOLs of the keypair to the rollup account of the recipient
+    DepositTo {
+        #[arg(long)]
+        chain_id: u64,
+        /// Rollup account the funds are minted on
+        #[arg(long)]
+        to: Address,
+        /// Amount in lamports
+        #[arg(long, conflicts_with = "wei", required_unless_present = "wei")]
+        lamports: Option<u64>,
+        /// Amount in wei, multiple of 10^9, decimal or 0x-prefixed hex
+        #[arg(long, value_parser = parse_u256)]
+        wei: Option<U256>,
+        /// Keypair file of the owner of the SOLs
+        #[arg(long)]
+        keypair: PathBuf,
+        /// JSON-RPC url of the rollup, the hash of the deposit is read from its blocks
+        #[arg(long)]
+        rpc_url: String,
+        /// Maximum time to wait for the deposit to be included in a block, in seconds
+        #[arg(long, default_value_t = 60)]
+        timeout: u64,
+    },
+    /// Register the owner of a rollup configured in the config
+    RegOwner {
+        #[arg(long)]
+        chain_id: u64,
+        /// Keypair file of the registry authority
+        #[arg(long)]
+        authority: PathBuf,
+    },
+    /// List the rollups registered in the Rome-EVM programs of the config
+    Rollups,
+    /// Inspect the payers of a rollup: balances, holders and address lookup tables
+    Payers {
+        #[arg(long)]
+        chain_id: u64,
+    },
+    /// Check the config against the Solana cluster: rollup programs, chain ids and payers
+    Validate,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    let level = if cli.verbose {
+        tracing::Level::INFO
+    } else {
+        tracing::Level::WARN
+    };
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_max_level(level)
+        .init();
+
+    match run(cli).await {
+        Ok(value) => println!("{:#}", value),
+        Err(e) => {
+            println!("{:#}", error_json(&e));
+            std::process::exit(1);
+        }
+    }
+}
+
+// output of a failed command, the error with its causes
+fn error_json(e: &anyhow::Error) -> Value {
+    json!({ "error": format!("{:#}", e) })
+}
+
+async fn run(cli: Cli) -> anyhow::Result<Value> {
+    let config = load_config(cli.config).await?;
+    if let Command::Validate = cli.command {
+        config.validate().await?;
+        return Ok(json!({ "valid": true }));
+    }
+
+    let rome = Rome::new_with_config(config.clone()).await?;
+
+    match cli.command {
+        Command::SendRawTx { rlp: raw } => {
+            let tx: Transaction = rlp::decode(&raw)?;
+            let hash = TxHash::from(keccak256(&raw));
+
+            let mut rome_tx = rome
+                .compose_rollup_tx(RheaTx::new(EthSignedTxTuple::from(&tx)))
+                .await?;
+            let signature = rome.send_and_confirm(&mut *rome_tx).await?;
+
+            Ok(json!({
+                "hash": hash,
+                "signature": signature.to_string(),
+            }))
+        }
+        Command::EstimateGas {
+            chain_id,
+            from,
+            to,
+            data,
+            value,
+        } => {
+            let tx = TransactionRequest {
+                from,
+                to: to.map(Into::into),
+                data,
+                value,
+                chain_id: Some(chain_id.into()),
+                ..Default::default()
+            };
+
+            Ok(json!({ "gas": rome.estimate_gas(&tx).await? }))
+        }
+        Command::Balance { chain_id, address } => {
+            Ok(json!({ "balance": rome.get_balance(address, chain_id).await? }))
+        }
+        Command::Nonce { chain_id, address } => {
+            Ok(json!({ "nonce": rome.transaction_count(address, chain_id).await? }))
+        }
+        Command::Code { chain_id, address } => {
+            Ok(json!({ "code": rome.get_code(address, chain_id).await? }))
+        }
+        Command::Storage {
+            chain_id,
+            address,
+            slot,
+        } => Ok(json!({ "value": rome.get_storage_at(address, slot, chain_id).await? })),
+        Command::Deposit {
+            chain_id,
+            rlp,
+            keypair,
+        } => {
+            let signer = SolanaKeyPayer::read_from_file(&keypair).await?;
+            let signature = rome.deposit(chain_id, &rlp, &signer).await?;
+
+            Ok(json!({ "signature": signature.to_string() }))
+        }
+        Command::DepositTo {
+            chain_id,
+            to,
+            lamports,
+            wei,
+            keypair,
+            rpc_url,
+            timeout,
+        } => {
+            let amount = match (lamports, wei) {
+                (Some(lamports), _) => DepositAmount::Lamports(lamports),
+                (None, Some(wei)) => DepositAmount::Wei(wei),
+                (None, None) => anyhow::bail!("either --lamports or --wei is required"),
+            };
+            let signer = SolanaKeyPayer::read_from_file(&keypair).await?;
+            let rollup = Provider::<Http>::try_from(rpc_url.as_str())?;
+            let (signature, hash) = rome
+                .deposit_to(
+                    chain_id,
+                    to,
+                    amount,
+                    &signer,
+                    &rollup,
+                    Duration::from_secs(timeout),
+                )
+                .await?;
+
+            Ok(json!({
+                "hash": hash,
+                "signature": signature.to_string(),
+            }))
+        }
+        Command::RegOwner {
+            chain_id,
+            authority,
+        } => {
+            let signer = SolanaKeyPayer::read_from_file(&authority).await?;
+            let signature = rome.reg_owner(chain_id, &signer).await?;
+
+            Ok(json!({ "signature": signature.to_string() }))
+        }
+        Command::Rollups => payers::rollups(&rome, &config).await,
+        Command::Payers { chain_id } => payers::payers(&rome, &config, chain_id).await,
+        Command::Validate => unreachable!("config is validated before connecting"),
+    }
+}
+
+async fn load_config(path: PathBuf) -> anyhow::Result<RomeConfig> {
+    let yml = matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("yml" | "yaml")
+    );
+
+    let config = if yml {
+        RomeConfig::load_yml(path).await?
+    } else {
+        RomeConfig::load_json(path).await?
+    };
+
+    Ok(config)
+}
+
+fn parse_u256(s: &str) -> Result<U256, String> {
+    match s.strip_prefix("0x") {
+        Some(hex) => U256::from_str_radix(hex, 16).map_err(|e| e.to_string()),
+        None => U256::from_dec_str(s).map_err(|e| e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(["rome", "--config", "rome.yml"].iter().chain(args))
+    }
+
+    #[test]
+    fn cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parse_deposit_to() {
+        let to = "0x00000000000000000000000000000000000000aa";
+        let cli = parse(&[
+            "deposit-to",
+            "--chain-id",
+            "1001",
+            "--to",
+            to,
+            "--wei",
+            "0x3b9aca00",
+            "--keypair",
+            "payer.json",
+            "--rpc-url",
+            "http://localhost:9090",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Command::DepositTo {
+                chain_id,
+                to: recipient,
+                lamports,
+                wei,
+                keypair,
+                rpc_url,
+                timeout,
+            } => {
+                assert_eq!(chain_id, 1001);
+                assert_eq!(recipient, to.parse::<Address>().unwrap());
+                assert_eq!(lamports, None);
+                assert_eq!(wei, Some(U256::from(1_000_000_000u64)));
+                assert_eq!(keypair, PathBuf::from("payer.json"));
+                assert_eq!(rpc_url, "http://localhost:9090");
+                assert_eq!(timeout, 60);
+            }
+            command => panic!("Unexpected command {:?}", command),
+        }
+
+        let to = ["deposit-to", "--chain-id", "1001", "--to", to];
+        let required = [
+            "--keypair",
+            "payer.json",
+            "--rpc-url",
+            "http://localhost:9090",
+        ];
+        // exactly one of the amounts is required
+        assert!(parse(&[&to[..], &required[..]].concat()).is_err());
+        assert!(
+            parse(&[&to[..], &["--lamports", "1", "--wei", "1"], &required[..]].concat()).is_err()
+        );
+    }
+
+    #[test]
+    fn parse_storage_slot() {
+        let address = "0x00000000000000000000000000000000000000aa";
+
+        for slot in ["16", "0x10"] {
+            let cli = parse(&["storage", "--chain-id", "1", address, slot]).unwrap();
+            assert!(matches!(cli.command, Command::Storage { slot, .. } if slot == U256::from(16)));
+        }
+
+        assert!(parse(&["storage", "--chain-id", "1", address, "0xzz"]).is_err());
+    }
+
+    #[test]
+    fn error_output() {
+        let error = anyhow::anyhow!("connection refused").context("Failed to read config file");
+
+        assert_eq!(
+            error_json(&error),
+            json!({ "error": "Failed to read config file: connection refused" })
+        );
+    }
+}
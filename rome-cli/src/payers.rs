@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+
+use ethers::types::Address;
+use rome_sdk::rome_evm_client::emulator;
+use rome_sdk::rome_evm_client::resources::PayerConfig;
+use rome_sdk::rome_evm_client::tx::TxBuilder;
+use rome_sdk::{Rome, RomeConfig};
+use serde_json::{json, Value};
+use solana_sdk::address_lookup_table::state::AddressLookupTable;
+use solana_sdk::pubkey::Pubkey;
+
+/// Rollups registered in each Rome-EVM program of the config
+pub async fn rollups(rome: &Rome, config: &RomeConfig) -> anyhow::Result<Value> {
+    // configured chain ids of each program
+    let mut programs: BTreeMap<&str, Vec<u64>> = BTreeMap::new();
+    for (chain_id, program) in config.rollups.iter() {
+        programs.entry(program).or_default().push(*chain_id);
+    }
+
+    let mut list = vec![];
+    for (program, mut configured) in programs {
+        configured.sort_unstable();
+
+        let registered = rome
+            .get_rollups(configured[0])
+            .await?
+            .iter()
+            .map(|rollup| rollup.chain)
+            .collect::<Vec<_>>();
+
+        list.push(json!({
+            "program": program,
+            "configured": configured,
+            "registered": registered,
+        }));
+    }
+
+    Ok(json!({ "programs": list }))
+}
+
+/// Balances, holders and address lookup tables of the payers of the rollup
+pub async fn payers(rome: &Rome, config: &RomeConfig, chain_id: u64) -> anyhow::Result<Value> {
+    let builder = rome.get_transaction_builder(chain_id)?;
+
+    let mut list = vec![];
+    for payer in config.payers_for(chain_id)? {
+        let pubkey = payer.signer().signer().await?.pubkey();
+        let balance = rome.solana().client().get_balance(&pubkey).await?;
+
+        let mut holders = vec![];
+        for (holder, fee_recipient) in holders(payer) {
+            holders.push(json!({
+                "holder": holder,
+                "fee_recipient": fee_recipient,
+                "alt": alt_state(builder, holder, &pubkey).await?,
+            }));
+        }
+
+        list.push(json!({
+            "signer": payer.signer().to_string(),
+            "pubkey": pubkey.to_string(),
+            "balance": balance,
+            "holders": holders,
+        }));
+    }
+
+    Ok(json!({ "chain_id": chain_id, "payers": list }))
+}
+
+// holder indexes of the payer, in the order the resources are allocated
+fn holders(payer: &PayerConfig) -> Vec<(u64, Option<Address>)> {
+    match (payer.fee_recipients(), payer.number_holders()) {
+        (Some(recipients), _) => recipients
+            .iter()
+            .enumerate()
+            .map(|(holder, recipient)| (holder as u64, Some(*recipient)))
+            .collect(),
+        (None, Some(number)) => (0..number).map(|holder| (holder, None)).collect(),
+        (None, None) => vec![],
+    }
+}
+
+// address lookup table of the holder, null if it is not allocated
+async fn alt_state(builder: &TxBuilder, holder: u64, payer: &Pubkey) -> anyhow::Result<Value> {
+    let mut data = vec![];
+    data.extend(holder.to_le_bytes());
+    data.extend(builder.chain_id.to_le_bytes());
+
+    let (program_id, payer, client) = (*builder.program_id(), *payer, builder.client_cloned());
+    // the blocking client is used on the threads of the pool
+    let alt = builder
+        .emulator_pool()
+        .run(move || {
+            let Some(key) = emulator::get_alt(&program_id, &data, &payer, client.clone())? else {
+                return Ok(None);
+            };
+            let account = client
+                .get_account_with_commitment(&key, client.commitment())?
+                .value;
+
+            Ok(Some((key, account)))
+        })
+        .await?;
+    let Some((key, account)) = alt else {
+        return Ok(Value::Null);
+    };
+    let Some(account) = account else {
+        return Ok(json!({ "address": key.to_string(), "closed": true }));
+    };
+    let state = AddressLookupTable::deserialize(&account.data)?;
+
+    Ok(json!({
+        "address": key.to_string(),
+        "addresses": state.addresses.len(),
+        "authority": state.meta.authority.map(|authority| authority.to_string()),
+        "last_extended_slot": state.meta.last_extended_slot,
+        "deactivation_slot": state.meta.deactivation_slot,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payer(json: &str) -> PayerConfig {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn holders_of_payer() {
+        let recipient = Address::repeat_byte(0xaa);
+        let with_recipients = payer(&format!(
+            r#"{{"payer_keypair": "/opt/payer.json", "fee_recipients": ["{:?}"]}}"#,
+            recipient
+        ));
+        assert_eq!(holders(&with_recipients), vec![(0, Some(recipient))]);
+
+        let with_holders = payer(r#"{"payer_keypair": "/opt/payer.json", "number_holders": 2}"#);
+        assert_eq!(holders(&with_holders), vec![(0, None), (1, None)]);
+    }
+}
@@ -1,22 +1,29 @@
+use rome_solana::observer::TxObserver;
+use rome_solana::signer::{sign_transaction, TxSigner};
 use rome_solana::tower::SolanaTower;
 use rome_solana::types::{AsyncAtomicRpcClient, SyncAtomicRpcClient};
 
 use crate::error::RomeEvmError::Custom;
 use crate::error::{ProgramResult, RomeEvmError};
 use crate::indexer::{
-    BlockParams, BlockParser, BlockProducer, MultiplexedSolanaClient, ProducedBlocks,
-    ProductionResult,
+    fee_history, BlockParams, BlockParser, BlockProducer, LogFilter, LogFilterBlocks,
+    MultiplexedSolanaClient, ProducedBlocks, ProductionResult,
 };
-use crate::indexer::{BlockType, EthereumBlockStorage, ProducerParams};
+use crate::indexer::config::RollupIndexerConfig;
+use crate::indexer::parsers::default_tx_parser::decode_transaction_from_rlp;
+use crate::indexer::{BlockType, EthereumBlockStorage, GenesisConfig, IndexerEvents, ProducerParams};
 use crate::indexer::{RollupIndexer, SolanaBlockLoader, SolanaBlockStorage, StandaloneIndexer};
-use crate::tx::{Iterable, TxBuilder};
+use crate::tx::{
+    build_deposit_rlp, deposit_slot, wait_deposit, DepositAmount, Iterable, SentTx, TrackedTx,
+    TxBuilder, TxStatus, TxStatuses,
+};
 use crate::util::{check_accounts_len, check_exit_reason, RomeEvmUtil};
-use crate::Payer;
+use crate::{EmulatorPool, Payer};
 use async_trait::async_trait;
 use emulator::Emulation;
 use ethers::types::{
-    Address, BlockId, BlockNumber, Bytes, FeeHistory, Transaction as EthTransaction,
-    TransactionReceipt, TransactionRequest, TxHash, H256, U256, U64,
+    Address, BlockId, BlockNumber, Bytes, FeeHistory, Filter, FilterBlockOption, Log,
+    Transaction as EthTransaction, TransactionReceipt, TransactionRequest, TxHash, H256, U256, U64,
 };
 use ethers::utils::keccak256;
 use rome_evm::error::RomeProgramError::AccountNotFound;
@@ -26,10 +33,11 @@ use solana_sdk::{
     clock::Slot,
     commitment_config::{CommitmentConfig, CommitmentLevel},
     pubkey::Pubkey,
-    signer::{keypair::Keypair, Signer},
-    transaction::Transaction,
-    instruction::Instruction
+    message::{Message, VersionedMessage},
+    instruction::Instruction,
+    signature::Signature,
 };
+use std::future::Future;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
@@ -42,7 +50,12 @@ const TX_RETRIES: usize = 10;
 const RETRY_INTERVAL: Duration = Duration::from_secs(10);
 const INDEXING_INTERVAL_MS: u64 = 400;
 const MAX_FEE_HISTORY_BLOCKS: u64 = 1000;
-const BASE_FEE_PER_GAS: u64 = 1000;
+
+/// Default maximum number of blocks searched by [RomeEVMClient::get_logs]
+pub const DEFAULT_MAX_LOGS_BLOCK_RANGE: u64 = 10000;
+
+/// Default maximum number of logs returned by [RomeEVMClient::get_logs]
+pub const DEFAULT_MAX_LOGS: usize = 10000;
 
 /// Client component interacting with the instance of Rome-EVM smart-contract on Solana blockchain
 ///
@@ -57,9 +70,18 @@ pub struct RomeEVMClient {
 
     commitment_level: CommitmentLevel,
 
-    genesis_timestamp: U256,
+    // genesis used until the indexer stores one
+    genesis: GenesisConfig,
 
     gas_price: U256,
+
+    events: IndexerEvents,
+
+    tx_statuses: TxStatuses,
+
+    max_logs_block_range: u64,
+
+    max_logs: usize,
 }
 
 #[derive(Clone)]
@@ -81,7 +103,18 @@ impl BlockProducer for DummyBlockProducer {
         Ok(U64::from(self.next_block.load(Ordering::Relaxed) - 1))
     }
 
-    async fn get_block_params(&self, _block_number: U64) -> ProgramResult<BlockParams> {
+    async fn get_block_params(&self, block_number: U64) -> ProgramResult<BlockParams> {
+        // blocks are numbered on top of the genesis created on the first run of the indexer
+        if block_number.is_zero() {
+            let genesis = GenesisConfig::now();
+            return Ok(BlockParams {
+                hash: genesis.hash,
+                parent_hash: None,
+                number: block_number,
+                timestamp: genesis.timestamp,
+            });
+        }
+
         Err(Custom(
             "DummyBlockProducer does not support get_block_params".to_string(),
         ))
@@ -154,16 +187,39 @@ impl RomeEVMClient {
             tx_builder: TxBuilder::new(chain_id, program_id, sync_client, payers),
             solana,
             commitment_level,
-            genesis_timestamp: U256::from(
-                std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_millis(),
-            ),
+            genesis: GenesisConfig::now(),
             gas_price,
+            events: IndexerEvents::default(),
+            tx_statuses: TxStatuses::default(),
+            max_logs_block_range: DEFAULT_MAX_LOGS_BLOCK_RANGE,
+            max_logs: DEFAULT_MAX_LOGS,
         }
     }
 
+    /// Limit [RomeEVMClient::get_logs] to `max_block_range` blocks and `max_logs` logs
+    pub fn with_log_limits(mut self, max_block_range: u64, max_logs: usize) -> Self {
+        self.max_logs_block_range = max_block_range;
+        self.max_logs = max_logs;
+        self
+    }
+
+    /// Keep the statuses of the submitted transactions for `ttl` after their last update
+    pub fn with_tx_status_ttl(mut self, ttl: Duration) -> Self {
+        self.tx_statuses = TxStatuses::new(ttl);
+        self
+    }
+
+    /// Run the emulations of the client on the given [EmulatorPool]
+    pub fn with_emulator_pool(mut self, emulator_pool: EmulatorPool) -> Self {
+        self.tx_builder = self.tx_builder.with_emulator_pool(emulator_pool);
+        self
+    }
+
+    /// Events of the indexer started by the client: produced blocks, their logs and reorgs
+    pub fn events(&self) -> &IndexerEvents {
+        &self.events
+    }
+
     /// Start the indexer and consume blocks
     pub fn start_indexing<S: SolanaBlockStorage + 'static>(
         &self,
@@ -191,7 +247,8 @@ impl RomeEVMClient {
             self.ethereum_block_storage.clone(),
             Some(Arc::new(DummyBlockProducer::new())),
             max_slot_history,
-        );
+        )
+        .with_events(self.events.clone());
 
         StandaloneIndexer {
             solana_block_loader: Some(solana_block_loader),
@@ -200,6 +257,49 @@ impl RomeEVMClient {
         .start_indexing(start_slot, idx_started_oneshot, INDEXING_INTERVAL_MS)
     }
 
+    /// Start the indexer configured by [RollupIndexerConfig] and consume blocks
+    ///
+    /// * `config` - Rollup indexer config, blocks are numbered by the client if it has no block producer
+    /// * `solana_block_loader` - Loader of Solana blocks, None if the blocks are loaded by the relayer
+    /// * `solana_block_storage` - Storage of Solana blocks, see [StorageConfig]
+    /// * `start_slot` - Slot to start indexing from
+    ///
+    /// [StorageConfig]: crate::indexer::config::StorageConfig
+    pub fn start_indexing_with_config(
+        &self,
+        config: &RollupIndexerConfig,
+        solana_block_loader: Option<SolanaBlockLoader>,
+        solana_block_storage: Arc<dyn SolanaBlockStorage>,
+        start_slot: Option<Slot>,
+    ) -> ProgramResult<JoinHandle<ProgramResult<()>>> {
+        let block_parser = config
+            .block_parser
+            .init(solana_block_storage.clone(), Some(*self.program_id()));
+
+        let block_producer: Arc<dyn BlockProducer> = match &config.block_producer {
+            Some(block_producer) => block_producer.init()?,
+            None => Arc::new(DummyBlockProducer::new()),
+        };
+
+        let mut rollup_indexer = RollupIndexer::new(
+            block_parser,
+            solana_block_storage,
+            self.ethereum_block_storage.clone(),
+            Some(block_producer),
+            config.max_slot_history,
+        )
+        .with_events(self.events.clone());
+        if let Some(genesis) = config.genesis()? {
+            rollup_indexer = rollup_indexer.with_genesis(genesis);
+        }
+
+        Ok(StandaloneIndexer {
+            solana_block_loader,
+            rollup_indexer: Some(rollup_indexer),
+        }
+        .start_indexing(start_slot, None, INDEXING_INTERVAL_MS))
+    }
+
     /// Emulates a raw transaction using full set of resources
     ///
     /// * `rlp` - signed rlp bytes for the transaction to be emulated
@@ -216,14 +316,26 @@ impl RomeEVMClient {
     ///
     /// Returns transaction hash or error if transaction can not be executed
     pub async fn send_transaction(&self, rlp: Bytes) -> ProgramResult<TxHash> {
-        let (hash, mut tx) = self.prepare_transaction(rlp).await?;
+        Ok(self.send_transaction_with_handle(rlp).await?.hash())
+    }
 
-        self.solana
-            .send_and_confirm_tx_iterable(&mut *tx)
-            .await
-            .map_err(|err| Custom(err.to_string()))?;
+    /// Executes transaction in a Rollup smart-contract
+    ///
+    /// * `rlp` - rlp of transaction
+    ///
+    /// Returns [SentTx] holding the transaction hash and the signatures of Solana transactions,
+    /// used to wait for the receipt
+    pub async fn send_transaction_with_handle(&self, rlp: Bytes) -> ProgramResult<SentTx> {
+        let hash: TxHash = keccak256(rlp.as_ref()).into();
+        let signatures = self
+            .send_tracked(hash, &rlp, self.tx_builder.build_tx(rlp.clone(), hash))
+            .await?;
 
-        Ok(hash)
+        Ok(SentTx::new(
+            hash,
+            signatures,
+            self.ethereum_block_storage.clone(),
+        ))
     }
     /// Executes transaction consisting of rome-evm instruction and SVM-instructions
     ///
@@ -239,26 +351,88 @@ impl RomeEVMClient {
         alt_keys: Option<Vec<Pubkey>>,
     ) -> ProgramResult<TxHash> {
         let hash: TxHash = keccak256(rlp.as_ref()).into();
-        let mut tx = self.tx_builder.build_svm_tx(rlp, svm, alt_keys).await?;
-
-        self.solana
-            .send_and_confirm_tx_iterable(&mut *tx)
-            .await
-            .map_err(|err| Custom(err.to_string()))?;
+        self.send_tracked(hash, &rlp, self.tx_builder.build_svm_tx(rlp.clone(), svm, alt_keys))
+            .await?;
 
         Ok(hash)
     }
 
+    // sends the transaction built by `tx`, recording its status from queued to confirmed or failed.
+    // Transactions with an undecodable rlp are rejected without a status
+    async fn send_tracked(
+        &self,
+        hash: TxHash,
+        rlp: &Bytes,
+        tx: impl Future<Output = ProgramResult<Iterable>>,
+    ) -> ProgramResult<Vec<Signature>> {
+        decode_transaction_from_rlp(&rlp::Rlp::new(rlp))?;
+        self.tx_statuses.set(hash, TxStatus::Queued);
+
+        let tx = match tx.await {
+            Ok(tx) => tx,
+            Err(err) => {
+                self.tx_statuses.set(hash, TxStatus::Failed { error: err.to_string() });
+                return Err(err);
+            }
+        };
+
+        let mut tx = TrackedTx::new(tx, hash, self.tx_statuses.clone());
+        match self.solana.send_and_confirm_tx_iterable(&mut tx).await {
+            Ok(signatures) => {
+                let status = TxStatus::Confirmed {
+                    signatures: signatures.clone(),
+                };
+                self.tx_statuses.set(hash, status);
+                Ok(signatures)
+            }
+            Err(err) => {
+                self.tx_statuses.set(hash, TxStatus::from_error(&err));
+                Err(Custom(err.to_string()))
+            }
+        }
+    }
+
+    /// Returns status of a transaction submitted by the client or indexed by its indexer,
+    /// None if the transaction is unknown or its status expired
+    ///
+    /// * `hash` - Transaction hash
+    pub async fn get_transaction_status(&self, hash: &TxHash) -> ProgramResult<Option<TxStatus>> {
+        let status = self.tx_statuses.get(hash);
+        if status == Some(TxStatus::Indexed) {
+            return Ok(status);
+        }
+
+        if self
+            .ethereum_block_storage
+            .get_transaction_receipt(hash)
+            .await?
+            .is_some()
+        {
+            if status.is_some() {
+                self.tx_statuses.set(*hash, TxStatus::Indexed);
+            }
+
+            return Ok(Some(TxStatus::Indexed));
+        }
+
+        Ok(status)
+    }
+
     /// Returns balance of requested account on current block
     ///
     /// * `address` - address on an account
-    pub fn get_balance(&self, address: Address) -> ProgramResult<U256> {
-        let value = emulator::eth_get_balance(
-            self.program_id(),
-            &rome_evm::H160::from(address.0),
-            self.sync_rpc_client(),
-            self.tx_builder.chain_id,
-        )?;
+    pub async fn get_balance(&self, address: Address) -> ProgramResult<U256> {
+        let (program_id, client, chain_id) = self.emulator_args();
+        let value = self
+            .run_emulator(move || {
+                Ok(emulator::eth_get_balance(
+                    &program_id,
+                    &rome_evm::H160::from(address.0),
+                    client,
+                    chain_id,
+                )?)
+            })
+            .await?;
 
         let mut buf = [0; 32];
         value.to_big_endian(&mut buf);
@@ -290,12 +464,12 @@ impl RomeEVMClient {
     /// * `call` - Transaction request object
     ///
     /// Returns result of execution
-    pub fn call(&self, call: &TransactionRequest) -> ProgramResult<Bytes> {
-        let emulation = emulator::eth_call(
-            self.program_id(),
-            RomeEvmUtil::cast_transaction_request(call, self.tx_builder.chain_id),
-            self.sync_rpc_client(),
-        )?;
+    pub async fn call(&self, call: &TransactionRequest) -> ProgramResult<Bytes> {
+        let (program_id, client, chain_id) = self.emulator_args();
+        let call = RomeEvmUtil::cast_transaction_request(call, chain_id);
+        let emulation = self
+            .run_emulator(move || Ok(emulator::eth_call(&program_id, call, client)?))
+            .await?;
 
         check_exit_reason(&emulation)?;
         let vm = emulation.vm.expect("vm expected");
@@ -306,25 +480,30 @@ impl RomeEVMClient {
     /// Returns transaction count (nonce) of a requested account in the latest block
     ///
     /// * `address` - address of account
-    pub fn transaction_count(&self, address: Address) -> ProgramResult<U64> {
-        let value = emulator::eth_get_tx_count(
-            self.program_id(),
-            &rome_evm::H160::from(address.0),
-            self.sync_rpc_client(),
-            self.tx_builder.chain_id,
-        )?;
+    pub async fn transaction_count(&self, address: Address) -> ProgramResult<U64> {
+        let (program_id, client, chain_id) = self.emulator_args();
+        let value = self
+            .run_emulator(move || {
+                Ok(emulator::eth_get_tx_count(
+                    &program_id,
+                    &rome_evm::H160::from(address.0),
+                    client,
+                    chain_id,
+                )?)
+            })
+            .await?;
         Ok(value.into())
     }
 
     /// Estimate gas amount for a given transaction
     ///
     /// * `call` - transaction request to estimate gas
-    pub fn estimate_gas(&self, call: &TransactionRequest) -> ProgramResult<U256> {
-        let emulation = emulator::eth_estimate_gas(
-            self.program_id(),
-            RomeEvmUtil::cast_transaction_request(call, self.tx_builder.chain_id),
-            self.sync_rpc_client(),
-        )?;
+    pub async fn estimate_gas(&self, call: &TransactionRequest) -> ProgramResult<U256> {
+        let (program_id, client, chain_id) = self.emulator_args();
+        let call = RomeEvmUtil::cast_transaction_request(call, chain_id);
+        let emulation = self
+            .run_emulator(move || Ok(emulator::eth_estimate_gas(&program_id, call, client)?))
+            .await?;
         check_exit_reason(&emulation)?;
         check_accounts_len(&emulation)?;
 
@@ -333,29 +512,27 @@ impl RomeEVMClient {
 
     async fn get_block_number(&self, block_number: BlockId) -> ProgramResult<Option<U64>> {
         match block_number {
-            BlockId::Number(number) => match number {
-                BlockNumber::Latest => {
-                    if let Some(latest) = self.ethereum_block_storage.latest_block().await? {
-                        Ok(Some(latest))
-                    } else {
-                        Ok(Some(U64::zero()))
-                    }
-                }
-                BlockNumber::Number(number) => Ok(Some(number)),
-                other => Err(RomeEvmError::Custom(format!(
-                    "Block tag {:?} not supported",
-                    other
-                ))),
+            BlockId::Number(number) => match tag_block(number) {
+                TagBlock::Latest => Ok(Some(
+                    self.ethereum_block_storage
+                        .latest_block()
+                        .await?
+                        .unwrap_or_default(),
+                )),
+                // None until a block is finalized
+                TagBlock::Finalized => self.ethereum_block_storage.finalized_block().await,
+                TagBlock::Number(number) => Ok(Some(number)),
             },
             BlockId::Hash(hash) => self.ethereum_block_storage.get_block_number(&hash).await,
         }
     }
 
-    /// Returns a collection of historical gas information for a specified number of blocks
+    /// Returns a collection of historical gas information for a specified number of blocks,
+    /// computed from the gas reports of the indexed transactions
     ///
     /// * `count` - Number of blocks to retrieve fee history for.
-    /// * `block_number` - Starting block number for fee history retrieval.
-    /// * `reward_percentiles` - Percentiles (0 to 100) for fee distribution.
+    /// * `block_number` - Newest block of the range.
+    /// * `reward_percentiles` - Increasing percentiles (0 to 100) of the priority fees.
     pub async fn fee_history(
         &self,
         count: u64,
@@ -364,40 +541,89 @@ impl RomeEVMClient {
     ) -> ProgramResult<FeeHistory> {
         let count = count.min(MAX_FEE_HISTORY_BLOCKS);
         if let Some(block_number) = self.get_block_number(block_number).await? {
-            let block_number = block_number.as_u64();
-            let base_fee_per_gas = vec![U256::from(BASE_FEE_PER_GAS); count as usize];
-            let gas_used_ratio = vec![0.5; count as usize];
-            let oldest_block = U256::from(block_number.saturating_sub(count));
-
-            let rewards = vec![
-                reward_percentiles
-                    .iter()
-                    .map(|&p| U256::from(p as u64))
-                    .collect::<Vec<U256>>();
-                count as usize
-            ];
-
-            Ok(FeeHistory {
-                base_fee_per_gas,
-                gas_used_ratio,
+            let newest_block = block_number.as_u64();
+            let count = count.min(newest_block + 1);
+            let oldest_block = newest_block + 1 - count;
+
+            let blocks = if count > 0 {
+                self.ethereum_block_storage
+                    .get_block_gas(U64::from(oldest_block), block_number)
+                    .await?
+            } else {
+                vec![]
+            };
+
+            fee_history(
                 oldest_block,
-                reward: rewards,
-            })
+                count,
+                &blocks,
+                &reward_percentiles,
+                self.gas_price,
+            )
         } else {
             Err(RomeEvmError::Custom("Indexer is not started".to_string()))
         }
     }
 
+    /// Returns logs of the indexed blocks matching a given filter, fails if the range of blocks
+    /// or the number of logs exceeds the limits of the client
+    ///
+    /// * `filter` - Log filter, the range of blocks defaults to the latest block
+    pub async fn get_logs(&self, filter: &Filter) -> ProgramResult<Vec<Log>> {
+        let blocks = match filter.block_option {
+            FilterBlockOption::AtBlockHash(hash) => LogFilterBlocks::Hash(hash),
+            FilterBlockOption::Range {
+                from_block,
+                to_block,
+            } => {
+                let from = self.filter_block_number(from_block).await?;
+                let to = self.filter_block_number(to_block).await?;
+                if from > to {
+                    return Err(Custom(format!(
+                        "Invalid block range: from block {} is after to block {}",
+                        from, to
+                    )));
+                }
+                if (to - from).as_u64() >= self.max_logs_block_range {
+                    return Err(Custom(format!(
+                        "Block range of {} blocks exceeds the maximum of {}",
+                        (to - from).as_u64() + 1,
+                        self.max_logs_block_range
+                    )));
+                }
+
+                LogFilterBlocks::Range { from, to }
+            }
+        };
+
+        self.ethereum_block_storage
+            .get_logs(&LogFilter::new(blocks, filter).with_max_logs(self.max_logs))
+            .await
+    }
+
+    async fn filter_block_number(&self, number: Option<BlockNumber>) -> ProgramResult<U64> {
+        let number = number.unwrap_or(BlockNumber::Latest);
+
+        self.get_block_number(BlockId::Number(number))
+            .await?
+            .ok_or_else(|| Custom(format!("Block {:?} is not known yet", number)))
+    }
+
     /// Returns bytecode of a requested smart-contract in the latest block
     ///
     /// * `address` - Address of a smart-contract
-    pub fn get_code(&self, address: Address) -> ProgramResult<Bytes> {
-        let value = emulator::eth_get_code(
-            self.program_id(),
-            &rome_evm::H160::from(address.0),
-            self.sync_rpc_client(),
-            self.tx_builder.chain_id,
-        )?;
+    pub async fn get_code(&self, address: Address) -> ProgramResult<Bytes> {
+        let (program_id, client, chain_id) = self.emulator_args();
+        let value = self
+            .run_emulator(move || {
+                Ok(emulator::eth_get_code(
+                    &program_id,
+                    &rome_evm::H160::from(address.0),
+                    client,
+                    chain_id,
+                )?)
+            })
+            .await?;
         let bytes = value.into();
         Ok(bytes)
     }
@@ -417,10 +643,13 @@ impl RomeEVMClient {
     ) -> ProgramResult<Option<BlockType>> {
         if let Some(block_number) = self.get_block_number(block_id).await? {
             if block_number == U64::zero() {
-                Ok(Some(BlockType::genesis(
-                    self.genesis_timestamp,
-                    full_transactions,
-                )))
+                let genesis = self
+                    .ethereum_block_storage
+                    .get_genesis()
+                    .await?
+                    .unwrap_or_else(|| self.genesis.clone());
+
+                Ok(Some(BlockType::genesis(&genesis, full_transactions)))
             } else {
                 self.ethereum_block_storage
                     .get_block_by_number(block_number, full_transactions)
@@ -431,6 +660,90 @@ impl RomeEVMClient {
         }
     }
 
+    // number of the produced block or genesis, None if the block is not produced yet
+    async fn get_produced_block_number(&self, block_id: BlockId) -> ProgramResult<Option<U64>> {
+        let Some(number) = self.get_block_number(block_id).await? else {
+            return Ok(None);
+        };
+
+        if number.is_zero()
+            || self
+                .ethereum_block_storage
+                .get_slot_for_eth_block(number)
+                .await?
+                .is_some()
+        {
+            Ok(Some(number))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns receipts of all transactions of a block, None if the block is not produced
+    ///
+    /// * `block_id` - Number, tag or hash of the block
+    pub async fn get_block_receipts(
+        &self,
+        block_id: BlockId,
+    ) -> ProgramResult<Option<Vec<TransactionReceipt>>> {
+        let Some(number) = self.get_produced_block_number(block_id).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(
+            self.ethereum_block_storage
+                .get_block_receipts(number)
+                .await?,
+        ))
+    }
+
+    /// Returns a transaction by its block and its index in the block, None if the block is not produced
+    ///
+    /// * `block_id` - Number, tag or hash of the block
+    /// * `index` - Index of the transaction in the block
+    pub async fn get_transaction_by_block_and_index(
+        &self,
+        block_id: BlockId,
+        index: U64,
+    ) -> ProgramResult<Option<EthTransaction>> {
+        let Some(number) = self.get_produced_block_number(block_id).await? else {
+            return Ok(None);
+        };
+
+        self.ethereum_block_storage
+            .get_transaction_by_block_and_index(number, index)
+            .await
+    }
+
+    /// Returns the number of transactions in a block, None if the block is not produced
+    ///
+    /// * `block_id` - Number, tag or hash of the block
+    pub async fn get_block_transaction_count(
+        &self,
+        block_id: BlockId,
+    ) -> ProgramResult<Option<U64>> {
+        let Some(number) = self.get_produced_block_number(block_id).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(
+            self.ethereum_block_storage
+                .get_block_transaction_count(number)
+                .await?,
+        ))
+    }
+
+    /// Returns the number of uncles of a block, always zero as rollup blocks have no uncles.
+    /// None if the block is not produced
+    ///
+    /// * `block_id` - Number, tag or hash of the block
+    pub async fn get_uncle_count(&self, block_id: BlockId) -> ProgramResult<Option<U64>> {
+        Ok(self
+            .get_produced_block_number(block_id)
+            .await?
+            .map(|_| U64::zero()))
+    }
+
     /// Emulates a raw transaction using rome-evm emulator
     ///
     /// * `rlp` - signed rlp bytes for the transaction to be emulated
@@ -441,7 +754,9 @@ impl RomeEVMClient {
         let mut data = vec![0];
         data.extend_from_slice(&rlp);
 
-        let emulation = self.emulate(emulator::Instruction::DoTx, &data, &pkey)?;
+        let emulation = self
+            .emulate(emulator::Instruction::DoTx, &data, &pkey)
+            .await?;
         check_exit_reason(&emulation)?;
         check_accounts_len(&emulation)?;
 
@@ -459,7 +774,7 @@ impl RomeEVMClient {
     /// * `data` - Instruction data
     ///
     /// Returns result of emulation
-    pub fn emulate(
+    pub async fn emulate(
         &self,
         instruction: emulator::Instruction,
         data: &[u8],
@@ -468,13 +783,24 @@ impl RomeEVMClient {
         let mut bin = vec![instruction as u8];
         bin.extend(data);
 
-        emulator::emulate(
-            self.program_id(),
-            &bin,
-            payer,
-            self.tx_builder.client_cloned(),
-        )
-        .map_err(|err| err.into())
+        let (program_id, client, _) = self.emulator_args();
+        let payer = *payer;
+        self.run_emulator(move || Ok(emulator::emulate(&program_id, &bin, &payer, client)?))
+            .await
+    }
+
+    // runs the synchronous emulator on the pool of the transaction builder
+    async fn run_emulator<T, F>(&self, emulate: F) -> ProgramResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> ProgramResult<T> + Send + 'static,
+    {
+        self.tx_builder.emulator_pool().run(emulate).await
+    }
+
+    // owned arguments of the emulator: program id, rpc client and chain id
+    fn emulator_args(&self) -> (Pubkey, SyncAtomicRpcClient, u64) {
+        (*self.program_id(), self.sync_rpc_client(), self.chain_id())
     }
 
     /// Get the solana tower
@@ -482,6 +808,11 @@ impl RomeEVMClient {
         &self.solana
     }
 
+    /// Register an observer of the execution of transactions sent by the client
+    pub fn register_observer(&self, observer: Arc<dyn TxObserver>) {
+        self.solana.register_observer(observer);
+    }
+
     /// Get [AsyncAtomicRpcClient]
     pub fn rpc_client(&self) -> AsyncAtomicRpcClient {
         self.solana.client_cloned()
@@ -508,17 +839,23 @@ impl RomeEVMClient {
     /// * `slot` - The storage slot index to retrieve the value from.
     ///
     /// The corresponding storage value.
-    pub fn eth_get_storage_at(&self, address: Address, slot: U256) -> ProgramResult<U256> {
+    pub async fn eth_get_storage_at(&self, address: Address, slot: U256) -> ProgramResult<U256> {
         let mut buf = [0u8; 32];
         slot.to_big_endian(&mut buf);
 
-        let value = emulator::eth_get_storage_at(
-            self.program_id(),
-            &rome_evm::H160::from(address.0),
-            &rome_evm::U256::from_big_endian(&buf),
-            self.sync_rpc_client(),
-            self.chain_id(),
-        )?;
+        let (program_id, client, chain_id) = self.emulator_args();
+        let slot = rome_evm::U256::from_big_endian(&buf);
+        let value = self
+            .run_emulator(move || {
+                Ok(emulator::eth_get_storage_at(
+                    &program_id,
+                    &rome_evm::H160::from(address.0),
+                    &slot,
+                    client,
+                    chain_id,
+                )?)
+            })
+            .await?;
 
         value.to_big_endian(&mut buf);
 
@@ -539,29 +876,57 @@ impl RomeEVMClient {
     /// precision of native SOL token is 10^9.
     ///
     /// This solana transaction must be signed by solana user's wallet private key.
-    pub async fn deposit(&self, rlp: &[u8], keypair: &Keypair) -> ProgramResult<()> {
-        let mut data = vec![emulator::Instruction::Deposit as u8];
-        data.extend(self.chain_id().to_le_bytes());
-        data.extend(rlp);
-
-        let emulation = emulator::emulate(
-            self.program_id(),
-            &data,
-            &keypair.pubkey(),
-            self.sync_rpc_client(),
-        )?;
-
-        let ix = self.tx_builder.build_ix(&emulation, data);
-        let blockhash = self.rpc_client().get_latest_blockhash().await?;
-        let tx = Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&keypair.pubkey()),
-            &[keypair],
-            blockhash,
-        );
-        let _ = self.rpc_client().send_and_confirm_transaction(&tx).await?;
+    ///
+    /// Returns the signature of the Solana transaction. See [RomeEVMClient::deposit_to] to build
+    /// the rlp from the recipient and the amount.
+    pub async fn deposit(&self, rlp: &[u8], signer: &dyn TxSigner) -> ProgramResult<Signature> {
+        let ix = self
+            .tx_builder
+            .build_deposit_ix(rlp, &signer.pubkey())
+            .await?;
+
+        self.send_signed(ix, signer).await
+    }
 
-        Ok(())
+    /// Deposits SOLs of the signer to the rollup account of the recipient
+    ///
+    /// * `recipient` - rollup account the funds are minted on
+    /// * `amount` - amount in lamports, or in wei multiple of 10^9
+    /// * `signer` - owner of the deposited SOLs, pays for the Solana transaction
+    /// * `timeout` - maximum time to wait for the indexer
+    ///
+    /// Returns the signature of the Solana transaction and the hash of the deposit transaction
+    /// once it is indexed
+    pub async fn deposit_to(
+        &self,
+        recipient: Address,
+        amount: DepositAmount,
+        signer: &dyn TxSigner,
+        timeout: Duration,
+    ) -> ProgramResult<(Signature, TxHash)> {
+        let rlp = build_deposit_rlp(recipient, amount)?;
+        let from_block = self
+            .ethereum_block_storage
+            .latest_block()
+            .await?
+            .unwrap_or_default()
+            + U64::one();
+
+        let signature = self.deposit(&rlp, signer).await?;
+        let slot = deposit_slot(self.solana.client(), signature).await?;
+
+        let hash = wait_deposit(
+            self.ethereum_block_storage.as_ref(),
+            signature,
+            slot,
+            from_block,
+            recipient,
+            amount.to_wei()?,
+            timeout,
+        )
+        .await?;
+
+        Ok((signature, hash))
     }
 
     /// Instruction is used to registry rollup owner.
@@ -569,35 +934,35 @@ impl RomeEVMClient {
     pub async fn reg_owner(
         &self,
         chain_id: u64,
-        registry_authority: &Keypair,
+        registry_authority: &dyn TxSigner,
     ) -> ProgramResult<()> {
-        let mut data = vec![emulator::Instruction::RegOwner as u8];
-        data.extend(chain_id.to_le_bytes());
+        let ix = self
+            .tx_builder
+            .build_reg_owner_ix(chain_id, &registry_authority.pubkey())
+            .await?;
+        self.send_signed(ix, registry_authority).await?;
 
-        let emulation = emulator::emulate(
-            self.program_id(),
-            &data,
-            &registry_authority.pubkey(),
-            self.sync_rpc_client(),
-        )?;
+        Ok(())
+    }
 
-        let ix = self.tx_builder.build_ix(&emulation, data);
+    // sends the instruction in a transaction paid and signed by the signer
+    async fn send_signed(
+        &self,
+        ix: Instruction,
+        signer: &dyn TxSigner,
+    ) -> ProgramResult<Signature> {
         let blockhash = self.rpc_client().get_latest_blockhash().await?;
-        let tx = Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&registry_authority.pubkey()),
-            &[registry_authority],
-            blockhash,
-        );
-        let _ = self.rpc_client().send_and_confirm_transaction(&tx).await?;
+        let message = Message::new_with_blockhash(&[ix], Some(&signer.pubkey()), &blockhash);
+        let tx = sign_transaction(VersionedMessage::Legacy(message), &[signer]).await?;
+        let signature = self.rpc_client().send_and_confirm_transaction(&tx).await?;
 
-        Ok(())
+        Ok(signature)
     }
 
-    pub fn get_rollups(&self) -> ProgramResult<Vec<OwnerInfo>> {
-        let rollups = emulator::get_rollups(self.program_id(), self.sync_rpc_client())?;
+    pub async fn get_rollups(&self) -> ProgramResult<Vec<OwnerInfo>> {
+        let (program_id, client, _) = self.emulator_args();
 
-        Ok(rollups)
+        self.run_emulator(move || Ok(emulator::get_rollups(&program_id, client)?)).await
     }
 
     pub async fn get_transaction_receipt(
@@ -635,3 +1000,43 @@ impl RomeEVMClient {
         Ok(acc.lamports)
     }
 }
+
+// block referred by a block tag, resolved to zero until such a block is produced
+#[derive(Debug, PartialEq, Eq)]
+enum TagBlock {
+    Latest,
+    Finalized,
+    Number(U64),
+}
+
+fn tag_block(number: BlockNumber) -> TagBlock {
+    match number {
+        // Pending transactions are included into blocks as soon as they are indexed
+        BlockNumber::Latest | BlockNumber::Pending => TagBlock::Latest,
+        // Blocks of finalized Solana slots can not be reverted
+        BlockNumber::Safe | BlockNumber::Finalized => TagBlock::Finalized,
+        BlockNumber::Earliest => TagBlock::Number(U64::zero()),
+        BlockNumber::Number(number) => TagBlock::Number(number),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn block_tags() {
+        assert_eq!(tag_block(BlockNumber::Latest), TagBlock::Latest);
+        assert_eq!(tag_block(BlockNumber::Pending), TagBlock::Latest);
+        assert_eq!(tag_block(BlockNumber::Safe), TagBlock::Finalized);
+        assert_eq!(tag_block(BlockNumber::Finalized), TagBlock::Finalized);
+        assert_eq!(
+            tag_block(BlockNumber::Earliest),
+            TagBlock::Number(U64::zero())
+        );
+        assert_eq!(
+            tag_block(BlockNumber::Number(U64::from(7))),
+            TagBlock::Number(U64::from(7))
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use crate::error::{ProgramResult, RomeEvmError};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+/// Maximum number of emulations running at the same time
+pub const DEFAULT_MAX_EMULATIONS: usize = 32;
+
+/// Maximum time of an emulation, including the wait for a free slot of the pool
+pub const DEFAULT_EMULATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Pool running the synchronous emulator on the blocking threads of the tokio runtime
+///
+/// The emulator reads the accounts with a blocking RPC client, running it on the executor
+/// threads stalls the other tasks. The number of concurrent emulations is limited to keep
+/// the blocking threads available to the rest of the process.
+#[derive(Clone)]
+pub struct EmulatorPool {
+    permits: Arc<Semaphore>,
+    timeout: Duration,
+}
+
+impl Default for EmulatorPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_EMULATIONS, DEFAULT_EMULATION_TIMEOUT)
+    }
+}
+
+impl EmulatorPool {
+    /// Creates a new [EmulatorPool]
+    ///
+    /// * `max_emulations` - maximum number of emulations running at the same time
+    /// * `timeout` - maximum time of an emulation, including the wait for a free slot
+    pub fn new(max_emulations: usize, timeout: Duration) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(max_emulations)),
+            timeout,
+        }
+    }
+
+    /// Runs the emulation on a blocking thread once a slot of the pool is free
+    ///
+    /// Fails with [RomeEvmError::EmulationTimeout] if the emulation is not completed in time.
+    /// The slot is released only when the emulation completes, even after the timeout.
+    pub async fn run<T, F>(&self, emulate: F) -> ProgramResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> ProgramResult<T> + Send + 'static,
+    {
+        let run = async {
+            let permit = self
+                .permits
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("Emulator pool semaphore is never closed");
+
+            tokio::task::spawn_blocking(move || {
+                let _permit = permit;
+                emulate()
+            })
+            .await
+            .map_err(|err| RomeEvmError::Custom(format!("Emulation task failed: {}", err)))?
+        };
+
+        tokio::time::timeout(self.timeout, run)
+            .await
+            .map_err(|_| RomeEvmError::EmulationTimeout(self.timeout))?
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn emulations_are_bounded_and_time_out() {
+        let pool = EmulatorPool::new(1, Duration::from_millis(500));
+        let (release, released) = std::sync::mpsc::channel::<()>();
+        // blocks until released by the test
+        let slow = move || {
+            let _ = released.recv();
+            Ok(1)
+        };
+
+        assert!(matches!(
+            pool.run(slow).await,
+            Err(RomeEvmError::EmulationTimeout(_))
+        ));
+        // the slow emulation still holds the only slot
+        assert!(matches!(
+            pool.run(|| Ok(2)).await,
+            Err(RomeEvmError::EmulationTimeout(_))
+        ));
+
+        release.send(()).unwrap();
+        while pool.permits.available_permits() == 0 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(pool.run(|| Ok(3)).await.unwrap(), 3);
+    }
+}
@@ -102,7 +102,22 @@ pub enum RomeEvmError {
     InstructionError(#[from] InstructionError),
     
     #[error("Error to compose SVM-composite transaction: {0}")]
-    SvmCompositeTxError(String)
+    SvmCompositeTxError(String),
+
+    #[error("Transaction #{0} of the bundle is too large or expensive to be executed atomically: {1} iterations, {2} compute units per iteration, {3} accounts")]
+    NonAtomicTx(usize, u64, u64, usize),
+
+    #[error("Receipt of transaction {0:?} is not indexed in time")]
+    ReceiptTimeout(ethers::types::TxHash),
+
+    #[error("Deposit amount {0} wei is not a multiple of 10^9")]
+    InvalidDepositAmount(ethers::types::U256),
+
+    #[error("Deposit {0} is not indexed in time")]
+    DepositTimeout(solana_sdk::signature::Signature),
+
+    #[error("Emulation is not completed in {0:?}")]
+    EmulationTimeout(std::time::Duration),
 }
 
 impl From<ClientError> for RomeEvmError {
@@ -5,8 +5,8 @@ use crate::indexer::block_producers::{
 use crate::indexer::parsers::block_parser::BlockParseMode;
 use crate::indexer::relayer::RelayerSolanaBlockStorage;
 use crate::indexer::{
-    inmemory, pg_storage, BlockParser, BlockProducer, EthereumBlockStorage, ProgramResult,
-    RollupIndexer, SolanaBlockStorage,
+    inmemory, pg_storage, BlockParser, BlockProducer, EthereumBlockStorage, GenesisConfig,
+    ProgramResult, RollupIndexer, SolanaBlockStorage,
 };
 use crate::indexer::{MultiplexedSolanaClient, SolanaBlockLoader};
 use serde::{Deserialize, Deserializer};
@@ -14,6 +14,7 @@ use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_program::pubkey::Pubkey;
 use solana_sdk::clock::Slot;
 use solana_sdk::commitment_config::CommitmentLevel;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
@@ -183,7 +184,7 @@ impl EthereumStorageConfig {
             EthereumStorageConfig::PgStorage { connection } => Arc::new(
                 pg_storage::EthereumBlockStorage::new(Arc::new(connection.init()?)),
             ),
-            EthereumStorageConfig::InMemory => Arc::new(inmemory::EthereumBlockStorage),
+            EthereumStorageConfig::InMemory => Arc::new(inmemory::EthereumBlockStorage::default()),
         })
     }
 }
@@ -220,6 +221,10 @@ pub struct RollupIndexerConfig {
 
     #[serde(default)]
     pub block_producer: Option<BlockProducerConfig>,
+
+    /// Path to the JSON file of the genesis block, fetched from the block producer if omitted
+    #[serde(default)]
+    pub genesis: Option<PathBuf>,
 }
 
 impl RollupIndexerConfig {
@@ -227,27 +232,36 @@ impl RollupIndexerConfig {
         self.block_producer.is_some()
     }
 
+    pub fn genesis(&self) -> ProgramResult<Option<GenesisConfig>> {
+        self.genesis
+            .as_ref()
+            .map(|path| GenesisConfig::load(path))
+            .transpose()
+    }
+
     pub fn init(
         &self,
         solana_block_storage: Arc<dyn SolanaBlockStorage>,
         ethereum_block_storage: Arc<dyn EthereumBlockStorage>,
         program_id: Option<Pubkey>,
-    ) -> RollupIndexer {
+    ) -> ProgramResult<RollupIndexer> {
         let block_parser = self
             .block_parser
             .init(solana_block_storage.clone(), program_id);
 
-        let block_producer = self
-            .block_producer
-            .as_ref()
-            .map(|c| c.init().expect("Failed to create block producer"));
+        let block_producer = self.block_producer.as_ref().map(|c| c.init()).transpose()?;
 
-        RollupIndexer::new(
+        let rollup_indexer = RollupIndexer::new(
             block_parser,
             solana_block_storage.clone(),
             ethereum_block_storage.clone(),
             block_producer,
             self.max_slot_history,
-        )
+        );
+
+        Ok(match self.genesis()? {
+            Some(genesis) => rollup_indexer.with_genesis(genesis),
+            None => rollup_indexer,
+        })
     }
 }
@@ -1,12 +1,12 @@
 use crate::error::ProgramResult;
 use crate::indexer::pending_blocks::PendingBlocks;
 use crate::indexer::produced_blocks::ProducedBlocks;
-use crate::indexer::BlockParseResult;
+use crate::indexer::{BlockGas, BlockParseResult, GenesisConfig, LogFilter};
 use async_trait::async_trait;
 use ethers::addressbook::Address;
 use ethers::prelude::{
-    Block, Bloom, Bytes, OtherFields, Transaction, TransactionReceipt, H256, H256 as TxHash, H64,
-    U256,
+    Block, Bloom, Bytes, Log, OtherFields, Transaction, TransactionReceipt, H256, H256 as TxHash,
+    H64, U256,
 };
 use ethers::types::U64;
 use jsonrpsee_core::Serialize;
@@ -43,6 +43,9 @@ pub struct ReproduceParams {
     pub expected_results: ProducedBlocks,
 }
 
+/// Gas limit of the Ethereum blocks
+pub const BLOCK_GAS_LIMIT: u64 = 48000000000000;
+
 const SHA3_UNCLES: [u8; 32] = [
     0x1d, 0xcc, 0x4d, 0xe8, 0xde, 0xc7, 0x5d, 0x7a, 0xab, 0x85, 0xb5, 0x67, 0xb6, 0xcc, 0xd4, 0x1a,
     0xd3, 0x12, 0x45, 0x1b, 0x94, 0x8a, 0x74, 0x13, 0xf0, 0xa1, 0x42, 0xfd, 0x40, 0xd4, 0x93, 0x47,
@@ -68,7 +71,7 @@ impl BlockType {
             hash,
             parent_hash,
             number,
-            gas_limit: U256::from(48000000000000u64),
+            gas_limit: U256::from(BLOCK_GAS_LIMIT),
             uncles_hash: H256::from(SHA3_UNCLES),
             author: Some(Address::default()),
             state_root: H256::zero(),
@@ -96,25 +99,26 @@ impl BlockType {
         }
     }
 
-    pub fn genesis(timestamp: U256, full_transactions: bool) -> Self {
-        if full_transactions {
-            BlockType::BlockWithTransactions(Self::base::<Transaction>(
-                Some(H256::zero()),
+    fn genesis_block<B>(genesis: &GenesisConfig) -> Block<B> {
+        Block::<B> {
+            state_root: genesis.state_root,
+            extra_data: genesis.extra_data.clone(),
+            ..Self::base(
+                Some(genesis.hash),
                 H256::zero(),
                 H256::zero(),
                 Some(U64::zero()),
                 U256::zero(),
-                timestamp,
-            ))
+                genesis.timestamp,
+            )
+        }
+    }
+
+    pub fn genesis(genesis: &GenesisConfig, full_transactions: bool) -> Self {
+        if full_transactions {
+            BlockType::BlockWithTransactions(Self::genesis_block(genesis))
         } else {
-            BlockType::BlockWithHashes(Self::base::<TxHash>(
-                Some(H256::zero()),
-                H256::zero(),
-                H256::zero(),
-                Some(U64::zero()),
-                U256::zero(),
-                timestamp,
-            ))
+            BlockType::BlockWithHashes(Self::genesis_block(genesis))
         }
     }
 }
@@ -141,9 +145,18 @@ pub trait EthereumBlockStorage: Send + Sync {
         parse_results: BTreeMap<Slot, BlockParseResult>,
     ) -> ProgramResult<()>;
 
+    /// Retrieves the parameters of the genesis block, if stored.
+    async fn get_genesis(&self) -> ProgramResult<Option<GenesisConfig>>;
+
+    /// Stores the parameters of the genesis block unless they are already stored.
+    async fn set_genesis(&self, genesis: &GenesisConfig) -> ProgramResult<()>;
+
     /// Retrieves the number of the most recently produced block.
     async fn latest_block(&self) -> ProgramResult<Option<U64>>;
 
+    /// Retrieves the number of the most recently produced block residing in a finalized Solana slot.
+    async fn finalized_block(&self) -> ProgramResult<Option<U64>>;
+
     /// Fetches a block number based on its hash (`H256`).
     async fn get_block_number(&self, hash: &H256) -> ProgramResult<Option<U64>>;
 
@@ -179,6 +192,27 @@ pub trait EthereumBlockStorage: Send + Sync {
     /// Retrieves full transaction details for a specific hash (`TxHash`).
     async fn get_transaction(&self, tx_hash: &TxHash) -> ProgramResult<Option<Transaction>>;
 
+    /// Retrieves the receipts of all transactions of a block by its number (`U64`), in order of execution.
+    async fn get_block_receipts(&self, number: U64) -> ProgramResult<Vec<TransactionReceipt>>;
+
+    /// Retrieves full transaction details by the number (`U64`) of its block and its index in the block.
+    async fn get_transaction_by_block_and_index(
+        &self,
+        number: U64,
+        index: U64,
+    ) -> ProgramResult<Option<Transaction>>;
+
+    /// Retrieves the number of transactions in a block by its number (`U64`).
+    async fn get_block_transaction_count(&self, number: U64) -> ProgramResult<U64>;
+
+    /// Retrieves the gas used by the produced blocks with numbers from `from` to `to` inclusive,
+    /// ordered by number. Blocks which are not produced are skipped.
+    async fn get_block_gas(&self, from: U64, to: U64) -> ProgramResult<Vec<BlockGas>>;
+
+    /// Retrieves the logs of the produced blocks matching the filter (`LogFilter`),
+    /// in order of execution.
+    async fn get_logs(&self, filter: &LogFilter) -> ProgramResult<Vec<Log>>;
+
     /// Maps an Ethereum block number (`U64`) to its corresponding Solana `Slot`.
     async fn get_slot_for_eth_block(&self, block_number: U64) -> ProgramResult<Option<Slot>>;
 
@@ -0,0 +1,202 @@
+use crate::error::RomeEvmError::Custom;
+use crate::indexer::parsers::block_parser::GasReport;
+use crate::indexer::ProgramResult;
+use crate::indexer::BLOCK_GAS_LIMIT;
+use ethers::types::{FeeHistory, U256, U64};
+
+/// Gas used by a produced block and the gas reports of its transactions, in order of execution
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockGas {
+    pub number: U64,
+    pub gas_used: U256,
+    pub gas_reports: Vec<GasReport>,
+}
+
+impl BlockGas {
+    // reports of the transactions paying for gas, deposits and system transactions are free
+    fn paid_reports(&self) -> impl Iterator<Item = &GasReport> {
+        self.gas_reports
+            .iter()
+            .filter(|report| !report.gas_price.is_zero())
+    }
+
+    // lowest gas price paid in the block, None if the block has no paid transactions
+    fn base_fee(&self) -> Option<U256> {
+        self.paid_reports().map(|report| report.gas_price).min()
+    }
+
+    fn gas_used_ratio(&self) -> f64 {
+        self.gas_used.low_u128() as f64 / BLOCK_GAS_LIMIT as f64
+    }
+
+    // priority fees over the base fee at the percentiles of gas used by the paid transactions,
+    // computed the same way as Ethereum nodes do
+    fn rewards(&self, base_fee: U256, percentiles: &[f64]) -> Vec<U256> {
+        let mut txs = self
+            .paid_reports()
+            .map(|report| (report.gas_price.saturating_sub(base_fee), report.gas_value))
+            .collect::<Vec<_>>();
+
+        if txs.is_empty() {
+            return vec![U256::zero(); percentiles.len()];
+        }
+        txs.sort_by_key(|(reward, _)| *reward);
+
+        let gas_used = txs
+            .iter()
+            .fold(U256::zero(), |sum, (_, gas_value)| sum + gas_value)
+            .low_u128() as f64;
+        let mut idx = 0;
+        let mut sum_gas = txs[0].1;
+
+        percentiles
+            .iter()
+            .map(|percentile| {
+                let threshold = gas_used * percentile / 100.0;
+                while (sum_gas.low_u128() as f64) < threshold && idx < txs.len() - 1 {
+                    idx += 1;
+                    sum_gas += txs[idx].1;
+                }
+
+                txs[idx].0
+            })
+            .collect()
+    }
+}
+
+/// Computes the fee history of `count` blocks starting from `oldest_block`.
+///
+/// * `blocks` - Gas used by the blocks of the range, in order, missing blocks have no transactions
+/// * `reward_percentiles` - Increasing percentiles (0 to 100) of the priority fees
+/// * `gas_price` - Gas price of the rollup, the base fee of blocks without transactions
+///   and of the block following the range
+pub fn fee_history(
+    oldest_block: u64,
+    count: u64,
+    blocks: &[BlockGas],
+    reward_percentiles: &[f64],
+    gas_price: U256,
+) -> ProgramResult<FeeHistory> {
+    let valid = reward_percentiles
+        .iter()
+        .all(|percentile| (0.0..=100.0).contains(percentile))
+        && reward_percentiles.windows(2).all(|pair| pair[0] <= pair[1]);
+    if !valid {
+        return Err(Custom(format!(
+            "Invalid reward percentiles {:?}: must be increasing values from 0 to 100",
+            reward_percentiles
+        )));
+    }
+
+    let mut base_fee_per_gas = Vec::with_capacity(count as usize + 1);
+    let mut gas_used_ratio = Vec::with_capacity(count as usize);
+    let mut reward = Vec::with_capacity(count as usize);
+
+    let mut blocks = blocks.iter().peekable();
+    for number in oldest_block..oldest_block + count {
+        let empty = BlockGas {
+            number: U64::from(number),
+            ..Default::default()
+        };
+        let block = blocks
+            .next_if(|block| block.number.as_u64() == number)
+            .unwrap_or(&empty);
+
+        let base_fee = block.base_fee().unwrap_or(gas_price);
+        base_fee_per_gas.push(base_fee);
+        gas_used_ratio.push(block.gas_used_ratio());
+        if !reward_percentiles.is_empty() {
+            reward.push(block.rewards(base_fee, reward_percentiles));
+        }
+    }
+    base_fee_per_gas.push(gas_price);
+
+    Ok(FeeHistory {
+        base_fee_per_gas,
+        gas_used_ratio,
+        oldest_block: U256::from(oldest_block),
+        reward,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn block(number: u64, txs: &[(u64, u64)]) -> BlockGas {
+        let gas_reports = txs
+            .iter()
+            .map(|(gas_value, gas_price)| GasReport {
+                gas_value: U256::from(*gas_value),
+                gas_price: U256::from(*gas_price),
+                gas_recipient: None,
+            })
+            .collect::<Vec<_>>();
+
+        BlockGas {
+            number: U64::from(number),
+            gas_used: gas_reports
+                .iter()
+                .fold(U256::zero(), |sum, report| sum + report.gas_value),
+            gas_reports,
+        }
+    }
+
+    #[test]
+    fn fee_history_from_gas_reports() {
+        let blocks = [
+            block(10, &[(21000, 10), (50000, 30), (29000, 20)]),
+            block(12, &[(21000, 5)]),
+        ];
+
+        let history = fee_history(10, 3, &blocks, &[0.0, 50.0, 100.0], U256::from(7)).unwrap();
+
+        assert_eq!(history.oldest_block, U256::from(10));
+        assert_eq!(
+            history.base_fee_per_gas,
+            [10u64, 7, 5, 7].map(U256::from).to_vec()
+        );
+        assert_eq!(history.gas_used_ratio.len(), 3);
+        assert_eq!(history.gas_used_ratio[1], 0.0);
+        assert_eq!(history.gas_used_ratio[0], 100000.0 / BLOCK_GAS_LIMIT as f64);
+        assert_eq!(
+            history.reward,
+            vec![
+                // priority fees 0, 10, 20 weighted by 21000, 29000, 50000 gas
+                [0u64, 10, 20].map(U256::from).to_vec(),
+                vec![U256::zero(); 3],
+                vec![U256::zero(); 3],
+            ]
+        );
+    }
+
+    #[test]
+    fn deposits_are_not_priced() {
+        // the block starts with the free L1 attributes deposit
+        let blocks = [
+            block(10, &[(0, 0), (21000, 10), (29000, 20)]),
+            block(11, &[(50000, 0)]),
+        ];
+
+        let history = fee_history(10, 2, &blocks, &[0.0, 100.0], U256::from(7)).unwrap();
+
+        assert_eq!(
+            history.base_fee_per_gas,
+            [10u64, 7, 7].map(U256::from).to_vec()
+        );
+        assert_eq!(
+            history.reward,
+            vec![[0u64, 10].map(U256::from).to_vec(), vec![U256::zero(); 2]]
+        );
+    }
+
+    #[test]
+    fn invalid_percentiles() {
+        assert!(fee_history(0, 1, &[], &[50.0, 10.0], U256::one()).is_err());
+        assert!(fee_history(0, 1, &[], &[101.0], U256::one()).is_err());
+        assert!(fee_history(0, 1, &[], &[], U256::one())
+            .unwrap()
+            .reward
+            .is_empty());
+    }
+}
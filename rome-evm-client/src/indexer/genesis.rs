@@ -0,0 +1,90 @@
+use crate::error::RomeEvmError::Custom;
+use crate::indexer::{BlockProducer, ProgramResult};
+use ethers::types::{Bytes, H256, U256, U64};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Parameters of the genesis block of the rollup, block number 0
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenesisConfig {
+    /// Timestamp of the block, in seconds
+    pub timestamp: U256,
+
+    pub hash: H256,
+
+    #[serde(default)]
+    pub state_root: H256,
+
+    #[serde(default)]
+    pub extra_data: Bytes,
+}
+
+impl GenesisConfig {
+    /// Loads the genesis from a JSON file
+    pub fn load(path: &Path) -> ProgramResult<Self> {
+        let file = std::fs::read_to_string(path).map_err(|err| {
+            Custom(format!(
+                "Failed to read genesis file {}: {}",
+                path.display(),
+                err
+            ))
+        })?;
+
+        Ok(serde_json::from_str(&file)?)
+    }
+
+    /// Fetches the genesis from the parameters of block 0 of the block producer
+    pub async fn from_block_producer(block_producer: &dyn BlockProducer) -> ProgramResult<Self> {
+        let params = block_producer.get_block_params(U64::zero()).await?;
+
+        Ok(Self {
+            timestamp: params.timestamp,
+            hash: params.hash,
+            state_root: H256::zero(),
+            extra_data: Bytes::default(),
+        })
+    }
+
+    /// Genesis with zero hash created at the current time, used when the rollup has no
+    /// block producer to fetch the genesis from
+    pub fn now() -> Self {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("System time is before UNIX epoch")
+            .as_secs();
+
+        Self {
+            timestamp: U256::from(timestamp),
+            hash: H256::zero(),
+            state_root: H256::zero(),
+            extra_data: Bytes::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_genesis() {
+        let genesis: GenesisConfig = serde_json::from_str(
+            r#"{
+                "timestamp": "0x6720a4c0",
+                "hash": "0x2f0a8f3b7c1d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(genesis.timestamp, U256::from(0x6720a4c0u64));
+        assert_eq!(genesis.state_root, H256::zero());
+        assert!(genesis.extra_data.is_empty());
+
+        let json = serde_json::to_value(&genesis).unwrap();
+        assert_eq!(
+            serde_json::from_value::<GenesisConfig>(json).unwrap(),
+            genesis
+        );
+    }
+}
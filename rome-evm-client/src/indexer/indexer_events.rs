@@ -0,0 +1,176 @@
+use crate::indexer::{LogFilter, LogFilterBlocks};
+use ethers::types::{Block, Filter, Log, TxHash, U64};
+use futures::{Stream, StreamExt};
+use solana_sdk::clock::Slot;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Number of events kept for the subscribers lagging behind the indexer
+const EVENTS_CAPACITY: usize = 1024;
+
+/// Event of the rollup indexer
+#[derive(Clone, Debug)]
+pub enum IndexerEvent {
+    /// Block produced and committed to the storage
+    NewBlock(Block<TxHash>),
+
+    /// Logs of the blocks produced and committed to the storage, in order of execution.
+    /// Logs of the blocks rolled back by a reorg are sent with `removed` set
+    /// before the [IndexerEvent::Reorg] event.
+    Logs(Vec<Log>),
+
+    /// Blocks starting from the Solana slot were rolled back
+    Reorg { from_slot: Slot },
+}
+
+/// Broadcast of the events of the rollup indexer
+///
+/// Subscribers receive the events emitted after the subscription. Streams of the subscribers
+/// lagging behind by more than [EVENTS_CAPACITY] events end, the subscribers resubscribe
+/// and fetch the missed blocks and logs from the storage.
+#[derive(Clone)]
+pub struct IndexerEvents {
+    sender: broadcast::Sender<IndexerEvent>,
+}
+
+impl Default for IndexerEvents {
+    fn default() -> Self {
+        Self::new(EVENTS_CAPACITY)
+    }
+}
+
+impl IndexerEvents {
+    /// Creates a new [IndexerEvents] keeping up to `capacity` events for every subscriber
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    pub(crate) fn has_subscribers(&self) -> bool {
+        self.sender.receiver_count() > 0
+    }
+
+    pub(crate) fn send(&self, event: IndexerEvent) {
+        // no subscribers is not an error
+        let _ = self.sender.send(event);
+    }
+
+    /// Stream of all the events, ends if the subscriber lags behind and skips events
+    pub fn events(&self) -> impl Stream<Item = IndexerEvent> + Send + 'static {
+        futures::stream::unfold(self.sender.subscribe(), |mut receiver| async move {
+            match receiver.recv().await {
+                Ok(event) => Some((event, receiver)),
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Indexer events subscriber skipped {} events", skipped);
+                    None
+                }
+                Err(RecvError::Closed) => None,
+            }
+        })
+    }
+
+    /// Stream of the produced blocks, `newHeads` subscription of `eth_subscribe`
+    pub fn new_heads(&self) -> impl Stream<Item = Block<TxHash>> + Send + 'static {
+        self.events().filter_map(|event| async move {
+            match event {
+                IndexerEvent::NewBlock(block) => Some(block),
+                _ => None,
+            }
+        })
+    }
+
+    /// Stream of the logs matching the addresses and topics of the filter,
+    /// `logs` subscription of `eth_subscribe`. Blocks of the filter are ignored.
+    /// Logs of the blocks rolled back by a reorg are streamed again with `removed` set.
+    pub fn logs(&self, filter: &Filter) -> impl Stream<Item = Log> + Send + 'static {
+        let filter = LogFilter::new(
+            LogFilterBlocks::Range {
+                from: U64::zero(),
+                to: U64::MAX,
+            },
+            filter,
+        );
+
+        self.events().flat_map(move |event| {
+            let logs = match event {
+                IndexerEvent::Logs(logs) => {
+                    logs.into_iter().filter(|log| filter.matches(log)).collect()
+                }
+                _ => vec![],
+            };
+
+            futures::stream::iter(logs)
+        })
+    }
+
+    /// Stream of the hashes of the transactions included into the produced blocks
+    pub fn transactions(&self) -> impl Stream<Item = TxHash> + Send + 'static {
+        self.new_heads()
+            .flat_map(|block| futures::stream::iter(block.transactions))
+    }
+
+    /// Stream of the Solana slots the blocks were rolled back from
+    pub fn reorgs(&self) -> impl Stream<Item = Slot> + Send + 'static {
+        self.events().filter_map(|event| async move {
+            match event {
+                IndexerEvent::Reorg { from_slot } => Some(from_slot),
+                _ => None,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ethers::types::{Address, H256};
+
+    #[tokio::test]
+    async fn streams_receive_events_after_subscription() {
+        let events = IndexerEvents::default();
+        events.send(IndexerEvent::Reorg { from_slot: 1 });
+
+        let contract = Address::random();
+        let mut new_heads = Box::pin(events.new_heads());
+        let mut logs = Box::pin(events.logs(&Filter::new().address(contract)));
+        let mut transactions = Box::pin(events.transactions());
+        let mut reorgs = Box::pin(events.reorgs());
+        assert!(events.has_subscribers());
+
+        let tx_hash = H256::random();
+        events.send(IndexerEvent::NewBlock(Block {
+            number: Some(U64::from(5u64)),
+            transactions: vec![tx_hash],
+            ..Default::default()
+        }));
+        events.send(IndexerEvent::Logs(vec![
+            Log {
+                address: Address::random(),
+                ..Default::default()
+            },
+            Log {
+                address: contract,
+                ..Default::default()
+            },
+        ]));
+        events.send(IndexerEvent::Reorg { from_slot: 2 });
+
+        assert_eq!(
+            new_heads.next().await.unwrap().number,
+            Some(U64::from(5u64))
+        );
+        assert_eq!(logs.next().await.unwrap().address, contract);
+        assert_eq!(transactions.next().await.unwrap(), tx_hash);
+        assert_eq!(reorgs.next().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn lagging_stream_ends() {
+        let events = IndexerEvents::new(1);
+        let mut reorgs = Box::pin(events.reorgs());
+
+        events.send(IndexerEvent::Reorg { from_slot: 1 });
+        events.send(IndexerEvent::Reorg { from_slot: 2 });
+
+        assert_eq!(reorgs.next().await, None);
+    }
+}
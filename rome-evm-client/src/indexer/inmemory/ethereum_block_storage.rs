@@ -1,12 +1,18 @@
 use crate::indexer::{
-    BlockParseResult, BlockType, ProducedBlocks, ProducerParams, ProgramResult, ReproduceParams,
+    BlockGas, BlockParseResult, BlockType, GenesisConfig, LogFilter, ProducedBlocks,
+    ProducerParams, ProgramResult, ReproduceParams,
 };
 use async_trait::async_trait;
-use ethers::types::{Transaction, TransactionReceipt, H256, U64};
+use ethers::types::{Log, Transaction, TransactionReceipt, H256, U64};
 use solana_sdk::clock::Slot;
 use std::collections::BTreeMap;
+use std::sync::Mutex;
 
-pub struct EthereumBlockStorage;
+/// Storage keeping only the genesis, no blocks are stored
+#[derive(Default)]
+pub struct EthereumBlockStorage {
+    genesis: Mutex<Option<GenesisConfig>>,
+}
 
 #[async_trait]
 impl crate::indexer::EthereumBlockStorage for EthereumBlockStorage {
@@ -29,10 +35,27 @@ impl crate::indexer::EthereumBlockStorage for EthereumBlockStorage {
         Ok(())
     }
 
+    async fn get_genesis(&self) -> ProgramResult<Option<GenesisConfig>> {
+        Ok(self
+            .genesis
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone())
+    }
+
+    async fn set_genesis(&self, genesis: &GenesisConfig) -> ProgramResult<()> {
+        *self.genesis.lock().unwrap_or_else(|e| e.into_inner()) = Some(genesis.clone());
+        Ok(())
+    }
+
     async fn latest_block(&self) -> ProgramResult<Option<U64>> {
         Ok(None)
     }
 
+    async fn finalized_block(&self) -> ProgramResult<Option<U64>> {
+        Ok(None)
+    }
+
     async fn get_block_number(&self, _hash: &H256) -> ProgramResult<Option<U64>> {
         Ok(None)
     }
@@ -72,6 +95,30 @@ impl crate::indexer::EthereumBlockStorage for EthereumBlockStorage {
         Ok(None)
     }
 
+    async fn get_block_receipts(&self, _number: U64) -> ProgramResult<Vec<TransactionReceipt>> {
+        Ok(vec![])
+    }
+
+    async fn get_transaction_by_block_and_index(
+        &self,
+        _number: U64,
+        _index: U64,
+    ) -> ProgramResult<Option<Transaction>> {
+        Ok(None)
+    }
+
+    async fn get_block_transaction_count(&self, _number: U64) -> ProgramResult<U64> {
+        Ok(U64::zero())
+    }
+
+    async fn get_block_gas(&self, _from: U64, _to: U64) -> ProgramResult<Vec<BlockGas>> {
+        Ok(vec![])
+    }
+
+    async fn get_logs(&self, _filter: &LogFilter) -> ProgramResult<Vec<Log>> {
+        Ok(vec![])
+    }
+
     async fn get_slot_for_eth_block(&self, _block_number: U64) -> ProgramResult<Option<Slot>> {
         Ok(None)
     }
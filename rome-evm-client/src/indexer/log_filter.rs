@@ -0,0 +1,120 @@
+use ethers::types::{Address, Filter, Log, Topic, ValueOrArray, H256, U64};
+
+/// Blocks the logs are searched in
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogFilterBlocks {
+    /// Produced blocks with numbers from `from` to `to` inclusive
+    Range { from: U64, to: U64 },
+    /// Produced block with the hash
+    Hash(H256),
+}
+
+/// Filter of the logs stored in [EthereumBlockStorage]
+///
+/// [EthereumBlockStorage]: crate::indexer::EthereumBlockStorage
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogFilter {
+    pub blocks: LogFilterBlocks,
+
+    /// Addresses of the contracts emitting the logs, any address if empty
+    pub addresses: Vec<Address>,
+
+    /// Sets of the topics allowed at each position, any topic if the set is empty
+    pub topics: [Vec<H256>; 4],
+
+    /// Maximum number of logs returned, the query fails if more logs match
+    pub max_logs: Option<usize>,
+}
+
+impl LogFilter {
+    /// Creates a new [LogFilter] from the addresses and topics of the Ethereum filter,
+    /// in the blocks resolved by the caller
+    pub fn new(blocks: LogFilterBlocks, filter: &Filter) -> Self {
+        let addresses = match &filter.address {
+            None => vec![],
+            Some(ValueOrArray::Value(address)) => vec![*address],
+            Some(ValueOrArray::Array(addresses)) => addresses.clone(),
+        };
+
+        Self {
+            blocks,
+            addresses,
+            topics: filter.topics.each_ref().map(topic_set),
+            max_logs: None,
+        }
+    }
+
+    /// Fails the query if more than `max_logs` logs match
+    pub fn with_max_logs(mut self, max_logs: usize) -> Self {
+        self.max_logs = Some(max_logs);
+        self
+    }
+
+    /// Checks the address and the topics of the log, the blocks are not checked
+    pub fn matches(&self, log: &Log) -> bool {
+        if !self.addresses.is_empty() && !self.addresses.contains(&log.address) {
+            return false;
+        }
+
+        self.topics
+            .iter()
+            .enumerate()
+            .all(|(position, set)| match log.topics.get(position) {
+                _ if set.is_empty() => true,
+                Some(topic) => set.contains(topic),
+                None => false,
+            })
+    }
+}
+
+// a null topic, alone or in the set, matches any topic at the position
+fn topic_set(topic: &Option<Topic>) -> Vec<H256> {
+    match topic {
+        None | Some(ValueOrArray::Value(None)) => vec![],
+        Some(ValueOrArray::Value(Some(topic))) => vec![*topic],
+        Some(ValueOrArray::Array(topics)) if topics.iter().any(Option::is_none) => vec![],
+        Some(ValueOrArray::Array(topics)) => topics.iter().flatten().copied().collect(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn match_addresses_and_topics() {
+        let (contract, transfer, from, to) = (
+            Address::random(),
+            H256::random(),
+            H256::random(),
+            H256::random(),
+        );
+        let log = Log {
+            address: contract,
+            topics: vec![transfer, from, to],
+            ..Default::default()
+        };
+        let blocks = LogFilterBlocks::Hash(H256::zero());
+
+        let filter = Filter::new().address(contract).topic0(transfer).topic2(to);
+        assert!(LogFilter::new(blocks.clone(), &filter).matches(&log));
+
+        let filter = Filter::new()
+            .address(vec![Address::random(), contract])
+            .topic1(vec![H256::random(), from]);
+        assert!(LogFilter::new(blocks.clone(), &filter).matches(&log));
+
+        let filter = Filter::new().address(Address::random());
+        assert!(!LogFilter::new(blocks.clone(), &filter).matches(&log));
+
+        let filter = Filter::new().topic2(from);
+        assert!(!LogFilter::new(blocks.clone(), &filter).matches(&log));
+
+        // the log has no topic at the position
+        let filter = Filter::new().topic3(to);
+        assert!(!LogFilter::new(blocks.clone(), &filter).matches(&log));
+
+        let filter = Filter::new().topic1(vec![Some(H256::random()), None]);
+        assert!(LogFilter::new(blocks, &filter).matches(&log));
+    }
+}
@@ -1,7 +1,11 @@
 pub mod block_producers;
 pub mod config;
 mod ethereum_block_storage;
+mod fee_history;
+mod genesis;
+mod indexer_events;
 pub mod inmemory;
+mod log_filter;
 mod metrics_reporter;
 mod multiplexed_solana_client;
 pub mod parsers;
@@ -20,9 +24,14 @@ pub use block_producers::block_producer::BlockProducer;
 pub use block_producers::block_producer::ProductionResult;
 pub use ethereum_block_storage::{
     BlockType, EthereumBlockStorage, FinalizedBlock, ProducerParams, ReproduceParams,
+    BLOCK_GAS_LIMIT,
 };
 use ethers::addressbook::Address;
 use ethers::prelude::{H256, U256};
+pub use fee_history::{fee_history, BlockGas};
+pub use genesis::GenesisConfig;
+pub use indexer_events::{IndexerEvent, IndexerEvents};
+pub use log_filter::{LogFilter, LogFilterBlocks};
 pub use metrics_reporter::MetricsReporter;
 pub use multiplexed_solana_client::MultiplexedSolanaClient;
 pub use parsers::block_parser::{BlockParseResult, BlockParser, TxResult};
@@ -124,6 +124,21 @@ pub fn create_l1_attributes_tx(
     )
 }
 
+/// Solana slot set by the L1 attributes transaction, None for other transactions
+pub fn l1_block_number(tx: &Transaction) -> Option<Slot> {
+    let data = tx.input.as_ref();
+    if tx.transaction_type != Some(U64::from(0x7E))
+        || tx.to != Some(Address::from(L1_ATTRIBUTES_SC))
+        || !data.starts_with(&SET_L1_ATTRIBUTES_HASH)
+        || data.len() < 36
+    {
+        return None;
+    }
+
+    let number = U256::from_big_endian(&data[4..36]);
+    (number <= U256::from(u64::MAX)).then(|| number.as_u64())
+}
+
 pub fn update_if_user_deposited_tx(tx: &mut Transaction, l1_blockhash: &H256, l1_log_index: U256) {
     if tx.transaction_type != Some(U64::from(0x7E)) || tx.is_system_tx {
         return;
@@ -1,6 +1,6 @@
 pub mod block_parser;
 pub mod default_tx_parser;
-mod l1_attributes;
+pub(crate) mod l1_attributes;
 pub mod log_parser;
 mod tx_parser;
 
@@ -8,7 +8,9 @@ use crate::indexer::pending_blocks::{PendingBlocks, PendingL1Block, PendingL2Blo
 use crate::indexer::pg_storage::transaction_storage::TransactionStorage;
 use crate::indexer::pg_storage::types::{ReceiptParams, SlotStatus};
 use crate::indexer::produced_blocks::{BlockParams, ProducedBlocks};
-use crate::indexer::{pg_storage::PgPool, BlockParseResult, BlockType, TxResult};
+use crate::indexer::{
+    pg_storage::PgPool, BlockGas, BlockParseResult, BlockType, GenesisConfig, LogFilter, TxResult,
+};
 use async_trait::async_trait;
 use diesel::deserialize::FromSql;
 use diesel::pg::{Pg, PgValue};
@@ -20,7 +22,7 @@ use diesel::{
     RunQueryDsl, Selectable,
 };
 use ethers::abi::AbiEncode;
-use ethers::prelude::{Block, Transaction, TransactionReceipt, TxHash, H256, U256, U64};
+use ethers::prelude::{Block, Log, Transaction, TransactionReceipt, TxHash, H256, U256, U64};
 use ethers::types::Address;
 use rlp::{Decodable, Rlp};
 use solana_program::clock::Slot;
@@ -302,6 +304,50 @@ pub(crate) struct BlockTransactionRow {
     pub(crate) receipt_params: Option<serde_json::Value>,
 }
 
+impl BlockTransactionRow {
+    fn into_transaction(self) -> ProgramResult<Transaction> {
+        let mut tx = Transaction::decode(&Rlp::new(&self.rlp)).map_err(|_| {
+            Custom(format!(
+                "Failed to decode transaction {} from rlp",
+                self.tx_hash
+            ))
+        })?;
+
+        if let Some(receipt_params) = self.receipt_params {
+            let receipt_params: ReceiptParams = serde_json::from_value(receipt_params)?;
+            tx.block_hash = Some(receipt_params.blockhash);
+            tx.block_number = Some(receipt_params.block_number);
+            tx.transaction_index = Some(U64::from(receipt_params.tx_index));
+        };
+
+        Ok(tx)
+    }
+}
+
+#[derive(QueryableByName, Debug)]
+struct GenesisRow {
+    #[diesel(sql_type = Jsonb)]
+    params: serde_json::Value,
+}
+
+#[derive(QueryableByName, Debug)]
+struct TransactionCountRow {
+    #[diesel(sql_type = BigInt)]
+    count: i64,
+}
+
+#[derive(QueryableByName, Debug)]
+struct BlockGasRow {
+    #[diesel(sql_type = BigInt)]
+    number: i64,
+
+    #[diesel(sql_type = Text)]
+    block_gas_used: String,
+
+    #[diesel(sql_type = Nullable<Jsonb>)]
+    tx_result: Option<serde_json::Value>,
+}
+
 impl EthBlock {
     pub(crate) fn from_block_header_row(row: &BlockHeaderRow) -> ProgramResult<Self> {
         Ok(Self {
@@ -371,6 +417,10 @@ define_sql_function! {
     fn latest_eth_block() -> Nullable<BigInt>;
 }
 
+define_sql_function! {
+    fn latest_finalized_eth_block() -> Nullable<BigInt>;
+}
+
 define_sql_function! {
     fn get_block_number(hash: sql_types::VarChar) -> Nullable<BigInt>;
 }
@@ -597,12 +647,37 @@ impl crate::indexer::EthereumBlockStorage for EthereumBlockStorage {
         Ok(())
     }
 
+    async fn get_genesis(&self) -> ProgramResult<Option<GenesisConfig>> {
+        let rows: Vec<GenesisRow> =
+            diesel::sql_query("SELECT params FROM eth_genesis").load(&mut self.pool.get()?)?;
+
+        Ok(rows
+            .into_iter()
+            .next()
+            .map(|row| serde_json::from_value(row.params))
+            .transpose()?)
+    }
+
+    async fn set_genesis(&self, genesis: &GenesisConfig) -> ProgramResult<()> {
+        diesel::sql_query("INSERT INTO eth_genesis (params) VALUES ($1) ON CONFLICT DO NOTHING")
+            .bind::<Jsonb, _>(serde_json::to_value(genesis)?)
+            .execute(&mut self.pool.get()?)?;
+
+        Ok(())
+    }
+
     async fn latest_block(&self) -> ProgramResult<Option<U64>> {
         Ok(diesel::select(latest_eth_block())
             .get_result::<Option<i64>>(&mut self.pool.get()?)?
             .map(|v| v.into()))
     }
 
+    async fn finalized_block(&self) -> ProgramResult<Option<U64>> {
+        Ok(diesel::select(latest_finalized_eth_block())
+            .get_result::<Option<i64>>(&mut self.pool.get()?)?
+            .map(|v| v.into()))
+    }
+
     async fn get_block_number(&self, hash: &H256) -> ProgramResult<Option<U64>> {
         Ok(diesel::select(get_block_number(hash.encode_hex()))
             .get_result::<Option<i64>>(&mut self.pool.get()?)?
@@ -638,24 +713,10 @@ impl crate::indexer::EthereumBlockStorage for EthereumBlockStorage {
                     .bind::<BigInt, _>(number.as_u64() as i64)
                     .load(&mut self.pool.get()?)?;
 
-            let mut txs = Vec::with_capacity(rows.len());
-            for row in rows {
-                let mut tx = Transaction::decode(&Rlp::new(&row.rlp)).map_err(|_| {
-                    Custom(format!(
-                        "Failed to decode transaction {} from rlp",
-                        row.tx_hash
-                    ))
-                })?;
-
-                if let Some(receipt_params) = row.receipt_params {
-                    let receipt_params: ReceiptParams = serde_json::from_value(receipt_params)?;
-                    tx.block_hash = Some(receipt_params.blockhash);
-                    tx.block_number = Some(receipt_params.block_number);
-                    tx.transaction_index = Some(U64::from(receipt_params.tx_index));
-                };
-
-                txs.push(tx);
-            }
+            let txs = rows
+                .into_iter()
+                .map(BlockTransactionRow::into_transaction)
+                .collect::<ProgramResult<Vec<_>>>()?;
 
             BlockType::BlockWithTransactions(Block::<Transaction> {
                 transactions: txs,
@@ -746,6 +807,78 @@ impl crate::indexer::EthereumBlockStorage for EthereumBlockStorage {
         self.transaction_storage.get_transaction(tx_hash).await
     }
 
+    async fn get_block_receipts(&self, number: U64) -> ProgramResult<Vec<TransactionReceipt>> {
+        self.transaction_storage.get_block_receipts(number).await
+    }
+
+    async fn get_transaction_by_block_and_index(
+        &self,
+        number: U64,
+        index: U64,
+    ) -> ProgramResult<Option<Transaction>> {
+        // no block holds that many transactions
+        let Ok(index) = i64::try_from(index.as_u64()) else {
+            return Ok(None);
+        };
+
+        let rows: Vec<BlockTransactionRow> =
+            diesel::sql_query("SELECT * FROM get_block_transactions($1) OFFSET $2 LIMIT 1")
+                .bind::<BigInt, _>(number.as_u64() as i64)
+                .bind::<BigInt, _>(index)
+                .load(&mut self.pool.get()?)?;
+
+        rows.into_iter()
+            .next()
+            .map(BlockTransactionRow::into_transaction)
+            .transpose()
+    }
+
+    async fn get_block_transaction_count(&self, number: U64) -> ProgramResult<U64> {
+        let row: TransactionCountRow =
+            diesel::sql_query("SELECT COUNT(*) AS count FROM get_block_transaction_hashes($1)")
+                .bind::<BigInt, _>(number.as_u64() as i64)
+                .get_result(&mut self.pool.get()?)?;
+
+        Ok(U64::from(row.count as u64))
+    }
+
+    async fn get_block_gas(&self, from: U64, to: U64) -> ProgramResult<Vec<BlockGas>> {
+        let rows: Vec<BlockGasRow> = diesel::sql_query("SELECT * FROM get_block_gas($1, $2)")
+            .bind::<BigInt, _>(from.as_u64() as i64)
+            .bind::<BigInt, _>(to.as_u64() as i64)
+            .load(&mut self.pool.get()?)?;
+
+        let mut blocks: Vec<BlockGas> = vec![];
+        for row in rows {
+            let number = U64::from(row.number);
+            if blocks.last().map(|block| block.number) != Some(number) {
+                blocks.push(BlockGas {
+                    number,
+                    gas_used: U256::from_dec_str(&row.block_gas_used).unwrap_or_else(|_| {
+                        panic!(
+                            "DB data corrupted: Failed to parse block_gas_used from {:?}",
+                            row
+                        )
+                    }),
+                    gas_reports: vec![],
+                });
+            }
+
+            if let Some(tx_result) = row.tx_result {
+                let tx_result: TxResult = serde_json::from_value(tx_result)?;
+                if let Some(block) = blocks.last_mut() {
+                    block.gas_reports.push(tx_result.gas_report);
+                }
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    async fn get_logs(&self, filter: &LogFilter) -> ProgramResult<Vec<Log>> {
+        self.transaction_storage.get_logs(filter).await
+    }
+
     async fn get_slot_for_eth_block(&self, block_number: U64) -> ProgramResult<Option<Slot>> {
         Ok(
             diesel::select(get_slot_for_eth_block(block_number.as_u64() as i64))
@@ -0,0 +1,26 @@
+-- Returns gas used by the produced Ethereum blocks with numbers
+-- from from_number to to_number inclusive and the results of their transactions
+CREATE OR REPLACE FUNCTION get_block_gas(from_number BIGINT, to_number BIGINT)
+    RETURNS TABLE (
+                      number BIGINT,
+                      block_gas_used TEXT,
+                      tx_result JSONB
+                  )
+AS $$
+BEGIN
+    RETURN QUERY
+        SELECT
+            (eb.params).number,
+            eb.block_gas_used::TEXT,
+            etr.tx_result
+        FROM eth_block eb
+                 LEFT JOIN eth_block_txs ebt
+                           ON ebt.slot_number = eb.slot_number AND ebt.slot_block_idx = eb.slot_block_idx
+                 LEFT JOIN evm_tx_result etr
+                           ON etr.slot_number = ebt.slot_number AND etr.tx_hash = ebt.tx_hash
+        WHERE eb.params IS NOT NULL
+          AND (eb.params).number >= from_number
+          AND (eb.params).number <= to_number
+        ORDER BY (eb.params).number, ebt.tx_idx;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,14 @@
+DROP FUNCTION IF EXISTS get_logs(BIGINT, BIGINT, VARCHAR(66), VARCHAR(42)[], VARCHAR(66)[], VARCHAR(66)[], VARCHAR(66)[], VARCHAR(66)[]);
+
+CREATE OR REPLACE PROCEDURE clean_from_slot(
+    from_slot BIGINT
+)  LANGUAGE plpgsql AS $$
+BEGIN
+    DELETE FROM eth_block_txs WHERE slot_number >= from_slot;
+    DELETE FROM eth_block WHERE slot_number >= from_slot;
+    DELETE FROM evm_tx_result WHERE slot_number >= from_slot;
+END;
+$$;
+
+DROP INDEX IF EXISTS eth_block_txs_tx;
+DROP TABLE IF EXISTS eth_log;
@@ -0,0 +1,113 @@
+-- Logs of the transaction results, indexed to be filtered by address and topics
+CREATE TABLE eth_log (
+    slot_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_idx INTEGER NOT NULL,
+
+    PRIMARY KEY (slot_number, tx_hash, log_idx),
+    CONSTRAINT fk_slot_number_tx_hash
+        FOREIGN KEY (slot_number, tx_hash)
+            REFERENCES evm_tx_result(slot_number, tx_hash),
+
+    address VARCHAR(42) NOT NULL,
+    topic0 VARCHAR(66) DEFAULT NULL,
+    topic1 VARCHAR(66) DEFAULT NULL,
+    topic2 VARCHAR(66) DEFAULT NULL,
+    topic3 VARCHAR(66) DEFAULT NULL,
+    data BYTEA NOT NULL
+);
+
+CREATE INDEX eth_log_address ON eth_log(address);
+CREATE INDEX eth_log_topic0 ON eth_log(topic0);
+CREATE INDEX eth_log_topic1 ON eth_log(topic1);
+CREATE INDEX eth_log_topic2 ON eth_log(topic2);
+CREATE INDEX eth_log_topic3 ON eth_log(topic3);
+CREATE INDEX eth_block_txs_tx ON eth_block_txs(slot_number, tx_hash);
+
+-- Logs of the already indexed transaction results
+INSERT INTO eth_log (slot_number, tx_hash, log_idx, address, topic0, topic1, topic2, topic3, data)
+SELECT
+    etr.slot_number,
+    etr.tx_hash,
+    (l.idx - 1)::INTEGER,
+    LOWER(l.log->>'address'),
+    LOWER(l.log->'topics'->>0),
+    LOWER(l.log->'topics'->>1),
+    LOWER(l.log->'topics'->>2),
+    LOWER(l.log->'topics'->>3),
+    DECODE(SUBSTRING(l.log->>'data' FROM 3), 'hex')
+FROM evm_tx_result etr,
+     JSONB_ARRAY_ELEMENTS(etr.tx_result->'logs') WITH ORDINALITY AS l(log, idx)
+ON CONFLICT DO NOTHING;
+
+------------------------------------------------------------------------------------------------------------------------
+
+CREATE OR REPLACE PROCEDURE clean_from_slot(
+    from_slot BIGINT
+)  LANGUAGE plpgsql AS $$
+BEGIN
+    DELETE FROM eth_block_txs WHERE slot_number >= from_slot;
+    DELETE FROM eth_block WHERE slot_number >= from_slot;
+    DELETE FROM eth_log WHERE slot_number >= from_slot;
+    DELETE FROM evm_tx_result WHERE slot_number >= from_slot;
+END;
+$$;
+
+------------------------------------------------------------------------------------------------------------------------
+
+-- Returns logs of the produced Ethereum blocks matching the filter, in order of execution.
+-- NULL parameters match any value
+CREATE OR REPLACE FUNCTION get_logs(
+    from_number BIGINT,
+    to_number BIGINT,
+    blockhash_value VARCHAR(66),
+    addresses VARCHAR(42)[],
+    topics0 VARCHAR(66)[],
+    topics1 VARCHAR(66)[],
+    topics2 VARCHAR(66)[],
+    topics3 VARCHAR(66)[]
+)
+    RETURNS TABLE (
+                      tx_hash VARCHAR(66),
+                      log_idx INTEGER,
+                      address VARCHAR(42),
+                      topic0 VARCHAR(66),
+                      topic1 VARCHAR(66),
+                      topic2 VARCHAR(66),
+                      topic3 VARCHAR(66),
+                      data BYTEA,
+                      receipt_params JSONB
+                  )
+AS $$
+BEGIN
+    RETURN QUERY
+        SELECT
+            el.tx_hash,
+            el.log_idx,
+            el.address,
+            el.topic0,
+            el.topic1,
+            el.topic2,
+            el.topic3,
+            el.data,
+            etr.receipt_params
+        FROM eth_block eb
+                 INNER JOIN eth_block_txs ebt
+                            ON ebt.slot_number = eb.slot_number AND ebt.slot_block_idx = eb.slot_block_idx
+                 INNER JOIN evm_tx_result etr
+                            ON etr.slot_number = ebt.slot_number AND etr.tx_hash = ebt.tx_hash
+                 INNER JOIN eth_log el
+                            ON el.slot_number = etr.slot_number AND el.tx_hash = etr.tx_hash
+        WHERE eb.params IS NOT NULL
+          AND etr.receipt_params IS NOT NULL
+          AND (from_number IS NULL OR (eb.params).number >= from_number)
+          AND (to_number IS NULL OR (eb.params).number <= to_number)
+          AND (blockhash_value IS NULL OR (eb.params).blockhash = blockhash_value)
+          AND (addresses IS NULL OR el.address = ANY(addresses))
+          AND (topics0 IS NULL OR el.topic0 = ANY(topics0))
+          AND (topics1 IS NULL OR el.topic1 = ANY(topics1))
+          AND (topics2 IS NULL OR el.topic2 = ANY(topics2))
+          AND (topics3 IS NULL OR el.topic3 = ANY(topics3))
+        ORDER BY (eb.params).number, ebt.tx_idx, el.log_idx;
+END;
+$$ LANGUAGE plpgsql;
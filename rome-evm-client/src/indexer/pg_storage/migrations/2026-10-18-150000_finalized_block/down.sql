@@ -0,0 +1 @@
+DROP FUNCTION IF EXISTS latest_finalized_eth_block;
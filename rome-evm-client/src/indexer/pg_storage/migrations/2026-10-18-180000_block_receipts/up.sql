@@ -0,0 +1,27 @@
+-- Returns transactions of the produced Ethereum block with their results, in order of execution
+CREATE OR REPLACE FUNCTION get_block_receipts(block_number_value BIGINT)
+    RETURNS TABLE (
+                      tx_hash VARCHAR(66),
+                      rlp BYTEA,
+                      tx_result JSONB,
+                      receipt_params JSONB
+                  )
+AS $$
+BEGIN
+    RETURN QUERY
+        SELECT
+            ebt.tx_hash,
+            et.rlp,
+            etr.tx_result,
+            etr.receipt_params
+        FROM eth_block eb
+                 INNER JOIN eth_block_txs ebt
+                            ON ebt.slot_number = eb.slot_number AND ebt.slot_block_idx = eb.slot_block_idx
+                 INNER JOIN evm_tx_result etr
+                            ON etr.slot_number = eb.slot_number AND etr.tx_hash = ebt.tx_hash
+                 INNER JOIN evm_tx et
+                            ON et.tx_hash = etr.tx_hash
+        WHERE (eb.params).number = block_number_value
+        ORDER BY ebt.tx_idx;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,5 @@
+-- Parameters of the genesis Ethereum block, a single row
+CREATE TABLE eth_genesis (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    params JSONB NOT NULL
+);
@@ -0,0 +1,13 @@
+-- Returns number of the latest produced Ethereum block residing in a finalized Solana slot
+CREATE OR REPLACE FUNCTION latest_finalized_eth_block() RETURNS BIGINT AS $$
+DECLARE
+    result BIGINT;
+BEGIN
+    SELECT MAX(((eb.params).number)) INTO result
+    FROM eth_block eb
+             INNER JOIN sol_slot s ON s.slot_number = eb.slot_number
+    WHERE eb.params IS NOT NULL
+      AND s.status = 'Finalized'::slotstatus;
+    RETURN result;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,21 @@
+-- Returns number of the latest produced Ethereum block residing in a finalized Solana slot,
+-- NULL if no block is finalized yet. The latest finalized slot is looked up first,
+-- then the blocks are scanned backwards from it by the primary key.
+CREATE OR REPLACE FUNCTION latest_finalized_eth_block() RETURNS BIGINT AS $$
+DECLARE
+    finalized_slot BIGINT;
+    result BIGINT;
+BEGIN
+    SELECT MAX(slot_number) INTO finalized_slot
+    FROM sol_slot
+    WHERE status = 'Finalized'::slotstatus;
+
+    SELECT ((params).number) INTO result
+    FROM eth_block
+    WHERE params IS NOT NULL
+      AND slot_number <= finalized_slot
+    ORDER BY slot_number DESC, slot_block_idx DESC
+    LIMIT 1;
+    RETURN result;
+END;
+$$ LANGUAGE plpgsql;
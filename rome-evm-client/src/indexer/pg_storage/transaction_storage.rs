@@ -3,20 +3,21 @@ use crate::error::RomeEvmError::Custom;
 use crate::indexer::pending_blocks::PendingBlocks;
 use crate::indexer::pg_storage::{types::ReceiptParams, PgPool};
 use crate::indexer::produced_blocks::ProducedBlocks;
-use crate::indexer::{self, BlockParseResult, TxResult};
+use crate::indexer::{self, BlockParseResult, LogFilter, LogFilterBlocks, TxResult};
 use diesel::{
     self,
-    sql_types::{BigInt, Binary, Bytea, Json, Jsonb, Nullable, Text, VarChar},
+    sql_types::{Array, BigInt, Binary, Bytea, Integer, Json, Jsonb, Nullable, Text, VarChar},
     RunQueryDsl,
 };
 use diesel::{Connection, QueryableByName};
 use ethers::abi::AbiEncode;
 use ethers::prelude::{Bloom, Log, OtherFields, Transaction, TransactionReceipt, TxHash, U64};
-use ethers::types::U256;
+use ethers::types::{Address, H256, U256};
 use rlp::{Decodable, Rlp};
 use solana_program::clock::Slot;
 use std::collections::BTreeMap;
 use std::ops::Add;
+use std::str::FromStr;
 use std::sync::Arc;
 
 pub struct TransactionStorage {
@@ -44,6 +45,85 @@ struct TransactionRow {
     receipt_params: Option<serde_json::Value>,
 }
 
+#[derive(QueryableByName, Debug)]
+struct BlockReceiptRow {
+    #[diesel(sql_type = VarChar)]
+    tx_hash: String,
+
+    #[diesel(sql_type = Binary)]
+    rlp: Vec<u8>,
+
+    #[diesel(sql_type = Jsonb)]
+    tx_result: serde_json::Value,
+
+    #[diesel(sql_type = Nullable<Jsonb>)]
+    receipt_params: Option<serde_json::Value>,
+}
+
+#[derive(QueryableByName, Debug)]
+struct LogRow {
+    #[diesel(sql_type = VarChar)]
+    tx_hash: String,
+
+    #[diesel(sql_type = Integer)]
+    log_idx: i32,
+
+    #[diesel(sql_type = VarChar)]
+    address: String,
+
+    #[diesel(sql_type = Nullable<VarChar>)]
+    topic0: Option<String>,
+
+    #[diesel(sql_type = Nullable<VarChar>)]
+    topic1: Option<String>,
+
+    #[diesel(sql_type = Nullable<VarChar>)]
+    topic2: Option<String>,
+
+    #[diesel(sql_type = Nullable<VarChar>)]
+    topic3: Option<String>,
+
+    #[diesel(sql_type = Binary)]
+    data: Vec<u8>,
+
+    #[diesel(sql_type = Jsonb)]
+    receipt_params: serde_json::Value,
+}
+
+impl LogRow {
+    fn into_log(self) -> ProgramResult<Log> {
+        let receipt_params: ReceiptParams = serde_json::from_value(self.receipt_params.clone())?;
+        let log_index = receipt_params.first_log_index + U256::from(self.log_idx);
+        let topics = [&self.topic0, &self.topic1, &self.topic2, &self.topic3]
+            .into_iter()
+            .flatten()
+            .map(|topic| {
+                H256::from_str(topic).unwrap_or_else(|_| {
+                    panic!("DB data corrupted: Failed to parse topic from {:?}", self)
+                })
+            })
+            .collect();
+
+        Ok(Log {
+            address: Address::from_str(&self.address).unwrap_or_else(|_| {
+                panic!("DB data corrupted: Failed to parse address from {:?}", self)
+            }),
+            topics,
+            data: self.data.clone().into(),
+            block_hash: Some(receipt_params.blockhash),
+            block_number: Some(receipt_params.block_number),
+            transaction_hash: Some(TxHash::from_str(&self.tx_hash).unwrap_or_else(|_| {
+                panic!("DB data corrupted: Failed to parse tx hash from {:?}", self)
+            })),
+            transaction_index: Some(U64::from(receipt_params.tx_index)),
+            log_index: Some(log_index),
+            transaction_log_index: Some(log_index),
+            log_type: None,
+            removed: Some(false),
+        })
+    }
+}
+
 #[tracing::instrument(name = "pg_storage::new_receipt", skip(tx_result, receipt_params), fields(tx_hash = ?tx.hash))]
 fn new_receipt(
     tx: &Transaction,
@@ -124,9 +204,26 @@ impl TransactionStorage {
                         "INSERT INTO evm_tx_result (slot_number, tx_hash, tx_result) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING;",
                     )
                         .bind::<BigInt, _>(parse_result.slot_number as i64)
-                        .bind::<VarChar, _>(tx_hash)
+                        .bind::<VarChar, _>(tx_hash.clone())
                         .bind::<Json, _>(serde_json::to_value(tx_result)?)
                         .execute(conn)?;
+
+                    for (log_idx, log) in tx_result.logs.iter().enumerate() {
+                        diesel::sql_query(
+                            "INSERT INTO eth_log (slot_number, tx_hash, log_idx, address, topic0, topic1, topic2, topic3, data) \
+                                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) ON CONFLICT DO NOTHING;",
+                        )
+                            .bind::<BigInt, _>(parse_result.slot_number as i64)
+                            .bind::<VarChar, _>(tx_hash.clone())
+                            .bind::<Integer, _>(log_idx as i32)
+                            .bind::<VarChar, _>(format!("0x{:x}", log.address))
+                            .bind::<Nullable<VarChar>, _>(log.topics.first().map(|t| t.encode_hex()))
+                            .bind::<Nullable<VarChar>, _>(log.topics.get(1).map(|t| t.encode_hex()))
+                            .bind::<Nullable<VarChar>, _>(log.topics.get(2).map(|t| t.encode_hex()))
+                            .bind::<Nullable<VarChar>, _>(log.topics.get(3).map(|t| t.encode_hex()))
+                            .bind::<Bytea, _>(log.data.as_ref())
+                            .execute(conn)?;
+                    }
                 }
             }
 
@@ -171,6 +268,34 @@ impl TransactionStorage {
         Ok(results.last_key_value().map(|(_, receipt)| receipt.clone()))
     }
 
+    #[tracing::instrument(name = "pg_storage::get_block_receipts", skip(self))]
+    pub async fn get_block_receipts(&self, number: U64) -> ProgramResult<Vec<TransactionReceipt>> {
+        let rows: Vec<BlockReceiptRow> = diesel::sql_query("SELECT * FROM get_block_receipts($1)")
+            .bind::<BigInt, _>(number.as_u64() as i64)
+            .load(&mut self.pool.get()?)?;
+
+        let mut receipts = Vec::with_capacity(rows.len());
+        for row in rows {
+            let Some(receipt_params) = row.receipt_params else {
+                continue;
+            };
+
+            let mut tx = Transaction::decode(&Rlp::new(&row.rlp)).map_err(|_| {
+                Custom(format!(
+                    "Failed to decode transaction {} from rlp",
+                    row.tx_hash
+                ))
+            })?;
+            tx.from = tx.recover_from()?;
+
+            let receipt_params: ReceiptParams = serde_json::from_value(receipt_params)?;
+            let tx_result: TxResult = serde_json::from_value(row.tx_result)?;
+            receipts.push(new_receipt(&tx, tx_result, receipt_params));
+        }
+
+        Ok(receipts)
+    }
+
     #[tracing::instrument(name = "pg_storage::get_transaction", skip(self), fields(tx_hash = ?tx_hash))]
     pub async fn get_transaction(&self, tx_hash: &TxHash) -> ProgramResult<Option<Transaction>> {
         let rows = self.get_transaction_internal(tx_hash)?;
@@ -199,6 +324,59 @@ impl TransactionStorage {
         Ok(results.last_key_value().map(|(_, tx)| tx.clone()))
     }
 
+    #[tracing::instrument(name = "pg_storage::get_logs", skip(self))]
+    pub async fn get_logs(&self, filter: &LogFilter) -> ProgramResult<Vec<Log>> {
+        let (from_number, to_number, blockhash) = match filter.blocks {
+            LogFilterBlocks::Range { from, to } => {
+                (Some(from.as_u64() as i64), Some(to.as_u64() as i64), None)
+            }
+            LogFilterBlocks::Hash(hash) => (None, None, Some(hash.encode_hex())),
+        };
+
+        let addresses = (!filter.addresses.is_empty()).then(|| {
+            filter
+                .addresses
+                .iter()
+                .map(|address| format!("0x{:x}", address))
+                .collect::<Vec<_>>()
+        });
+        let [topics0, topics1, topics2, topics3] = filter.topics.each_ref().map(|topics| {
+            (!topics.is_empty()).then(|| {
+                topics
+                    .iter()
+                    .map(|topic| topic.encode_hex())
+                    .collect::<Vec<_>>()
+            })
+        });
+
+        // one more row to tell the results exceeding the maximum
+        let limit = filter.max_logs.map(|max_logs| max_logs as i64 + 1);
+
+        let rows: Vec<LogRow> =
+            diesel::sql_query("SELECT * FROM get_logs($1, $2, $3, $4, $5, $6, $7, $8) LIMIT $9")
+                .bind::<Nullable<BigInt>, _>(from_number)
+                .bind::<Nullable<BigInt>, _>(to_number)
+                .bind::<Nullable<VarChar>, _>(blockhash)
+                .bind::<Nullable<Array<VarChar>>, _>(addresses)
+                .bind::<Nullable<Array<VarChar>>, _>(topics0)
+                .bind::<Nullable<Array<VarChar>>, _>(topics1)
+                .bind::<Nullable<Array<VarChar>>, _>(topics2)
+                .bind::<Nullable<Array<VarChar>>, _>(topics3)
+                .bind::<Nullable<BigInt>, _>(limit)
+                .load(&mut self.pool.get()?)?;
+
+        if let Some(max_logs) = filter.max_logs {
+            if rows.len() > max_logs {
+                return Err(Custom(format!(
+                    "query returned more than {} results",
+                    max_logs
+                )));
+            }
+        }
+
+        rows.into_iter().map(LogRow::into_log).collect()
+    }
+
     #[tracing::instrument(
         name = "pg_storage::blocks_produced",
         skip(self, pending_blocks, produced_blocks)
@@ -4,8 +4,11 @@ use crate::indexer::block_producers::block_producer::BlockProducer;
 use crate::indexer::ethereum_block_storage::{ProducerParams, ReproduceParams};
 use crate::indexer::parsers::{default_tx_parser::DefaultTxParser, TxParser};
 use crate::indexer::produced_blocks::{BlockParams, ProducedBlocks};
-use crate::indexer::{BlockParseResult, BlockParser, EthereumBlockStorage, SolanaBlockStorage};
-use ethers::types::U64;
+use crate::indexer::{
+    BlockParseResult, BlockParser, BlockType, EthereumBlockStorage, GenesisConfig, IndexerEvent,
+    IndexerEvents, LogFilter, LogFilterBlocks, SolanaBlockStorage,
+};
+use ethers::types::{Filter, Log, U64};
 use solana_sdk::clock::Slot;
 use std::collections::BTreeMap;
 use std::ops::DerefMut;
@@ -25,6 +28,8 @@ pub struct RollupIndexer {
     ethereum_block_storage: Arc<dyn EthereumBlockStorage>,
     block_producer: Option<Arc<dyn BlockProducer>>,
     max_slot_history: Option<Slot>,
+    events: Option<IndexerEvents>,
+    genesis: Option<GenesisConfig>,
 }
 
 impl RollupIndexer {
@@ -42,9 +47,23 @@ impl RollupIndexer {
             ethereum_block_storage,
             block_producer,
             max_slot_history,
+            events: None,
+            genesis: None,
         }
     }
 
+    /// Stores `genesis` as the genesis block instead of fetching it from the block producer
+    pub fn with_genesis(mut self, genesis: GenesisConfig) -> Self {
+        self.genesis = Some(genesis);
+        self
+    }
+
+    /// Broadcasts the produced blocks, their logs and reorgs to the subscribers of `events`
+    pub fn with_events(mut self, events: IndexerEvents) -> Self {
+        self.events = Some(events);
+        self
+    }
+
     pub fn ethereum_block_storage(&self) -> Arc<dyn EthereumBlockStorage> {
         self.ethereum_block_storage.clone()
     }
@@ -64,6 +83,8 @@ impl RollupIndexer {
     }
 
     async fn pre_run(&self, start_slot: Option<Slot>, interval: Duration) -> ProgramResult<Slot> {
+        self.init_genesis().await?;
+
         let Some(block_producer) = &self.block_producer else {
             return Ok(start_slot.unwrap_or_default());
         };
@@ -104,6 +125,30 @@ impl RollupIndexer {
         })
     }
 
+    // Stores the genesis block on the first run. Configured genesis must match the stored one.
+    async fn init_genesis(&self) -> ProgramResult<()> {
+        if let Some(stored) = self.ethereum_block_storage.get_genesis().await? {
+            return match &self.genesis {
+                Some(genesis) if genesis != &stored => Err(Custom(format!(
+                    "Configured genesis {:?} does not match the stored genesis {:?}",
+                    genesis, stored
+                ))),
+                _ => Ok(()),
+            };
+        }
+
+        let genesis = match (&self.genesis, &self.block_producer) {
+            (Some(genesis), _) => genesis.clone(),
+            (None, Some(block_producer)) => {
+                GenesisConfig::from_block_producer(block_producer.as_ref()).await?
+            }
+            (None, None) => GenesisConfig::now(),
+        };
+
+        tracing::info!("Storing genesis block {:?}", genesis);
+        self.ethereum_block_storage.set_genesis(&genesis).await
+    }
+
     #[tracing::instrument(name = "rollup_indexer::reproduce_blocks_until_in_sync", skip(self), fields(height = ?last_known_block, batch_size = ?batch_size))]
     async fn reproduce_blocks_until_in_sync(
         &self,
@@ -214,9 +259,28 @@ impl RollupIndexer {
             from_slot = match reorg_event_rx.try_recv() {
                 Ok(reorg_slot) => {
                     tracing::warn!("Reorg event received, cleaning from slot {reorg_slot}");
+                    // the logs are not available once the blocks are cleaned
+                    let removed_logs = match &self.events {
+                        Some(events) if events.has_subscribers() => {
+                            self.removed_logs(reorg_slot).await.unwrap_or_else(|err| {
+                                tracing::warn!("Failed to load logs removed by reorg: {:?}", err);
+                                vec![]
+                            })
+                        }
+                        _ => vec![],
+                    };
+
                     self.ethereum_block_storage
                         .clean_from_slot(reorg_slot)
                         .await?;
+                    if let Some(events) = &self.events {
+                        if !removed_logs.is_empty() {
+                            events.send(IndexerEvent::Logs(removed_logs));
+                        }
+                        events.send(IndexerEvent::Reorg {
+                            from_slot: reorg_slot,
+                        });
+                    }
                     Ok(reorg_slot)
                 }
                 Err(err) => match err {
@@ -361,8 +425,7 @@ impl RollupIndexer {
                 if let Some((last_slot_number, last_block_idx, last_block_params)) =
                     production_result.produced_blocks.last_key_value()
                 {
-                    self.ethereum_block_storage
-                        .blocks_produced(&producer_params, production_result.produced_blocks)
+                    self.blocks_produced(&producer_params, production_result.produced_blocks)
                         .await?;
 
                     producer_params.parent_hash = Some(last_block_params.hash);
@@ -409,13 +472,107 @@ impl RollupIndexer {
             }
         }
 
+        self.blocks_produced(&producer_params, produced_blocks)
+            .await
+    }
+
+    async fn blocks_produced(
+        &self,
+        producer_params: &ProducerParams,
+        produced_blocks: ProducedBlocks,
+    ) -> ProgramResult<()> {
+        let block_numbers = produced_blocks
+            .iter()
+            .map(|(_, _, block_params)| block_params.number)
+            .collect::<Vec<_>>();
+
         self.ethereum_block_storage
-            .blocks_produced(&producer_params, produced_blocks)
+            .blocks_produced(producer_params, produced_blocks)
             .await?;
 
+        match &self.events {
+            Some(events) if events.has_subscribers() => {
+                if let Err(err) = self.notify_blocks_produced(events, &block_numbers).await {
+                    tracing::warn!("Failed to notify subscribers of produced blocks: {:?}", err);
+                }
+            }
+            _ => {}
+        }
+
         Ok(())
     }
 
+    async fn notify_blocks_produced(
+        &self,
+        events: &IndexerEvents,
+        block_numbers: &[U64],
+    ) -> ProgramResult<()> {
+        let (Some(from), Some(to)) = (block_numbers.first(), block_numbers.last()) else {
+            return Ok(());
+        };
+
+        for block_number in block_numbers {
+            if let Some(BlockType::BlockWithHashes(block)) = self
+                .ethereum_block_storage
+                .get_block_by_number(*block_number, false)
+                .await?
+            {
+                events.send(IndexerEvent::NewBlock(block));
+            }
+        }
+
+        let blocks = LogFilterBlocks::Range {
+            from: *from,
+            to: *to,
+        };
+        let logs = self
+            .ethereum_block_storage
+            .get_logs(&LogFilter::new(blocks, &Filter::new()))
+            .await?;
+        if !logs.is_empty() {
+            events.send(IndexerEvent::Logs(logs));
+        }
+
+        Ok(())
+    }
+
+    // logs of the blocks produced from the slot, marked as removed
+    async fn removed_logs(&self, from_slot: Slot) -> ProgramResult<Vec<Log>> {
+        let Some(latest) = self.ethereum_block_storage.latest_block().await? else {
+            return Ok(vec![]);
+        };
+
+        // blocks are scanned backwards, reorgs roll back a few recent slots
+        let mut first = latest + U64::one();
+        while !first.is_zero() {
+            match self
+                .ethereum_block_storage
+                .get_slot_for_eth_block(first - U64::one())
+                .await?
+            {
+                Some(slot) if slot >= from_slot => first -= U64::one(),
+                _ => break,
+            }
+        }
+        if first > latest {
+            return Ok(vec![]);
+        }
+
+        let blocks = LogFilterBlocks::Range {
+            from: first,
+            to: latest,
+        };
+        let mut logs = self
+            .ethereum_block_storage
+            .get_logs(&LogFilter::new(blocks, &Filter::new()))
+            .await?;
+        for log in logs.iter_mut() {
+            log.removed = Some(true);
+        }
+
+        Ok(logs)
+    }
+
     #[tracing::instrument(name = "rollup_indexer::get_blocks_params", skip(self), fields(from_block = ?from_block, to_block = ?to_block))]
     async fn get_blocks_params(
         &self,
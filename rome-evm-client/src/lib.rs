@@ -1,11 +1,13 @@
 mod client;
+mod emulator_pool;
 pub mod error;
 pub mod indexer;
 pub mod resources;
 pub mod tx;
 pub mod util;
 
-pub use client::RomeEVMClient;
+pub use client::{RomeEVMClient, DEFAULT_MAX_LOGS, DEFAULT_MAX_LOGS_BLOCK_RANGE};
+pub use emulator_pool::*;
 pub use emulator;
 pub use resources::*;
 pub use rome_evm;
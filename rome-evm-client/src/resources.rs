@@ -1,12 +1,13 @@
 use crate::error::ProgramResult;
 use {
     ethers::types::Address,
-    rome_solana::payer::SolanaKeyPayer,
-    solana_sdk::{
-        pubkey::Pubkey,
-        signer::{keypair::Keypair, Signer},
+    rome_solana::{
+        payer::SolanaKeyPayer,
+        signer::{RemoteSignerConfig, SharedSigner},
     },
+    solana_sdk::pubkey::Pubkey,
     std::{
+        fmt::Display,
         mem::size_of,
         path::PathBuf,
         sync::{Arc, Mutex},
@@ -19,35 +20,79 @@ pub enum ResourceType {
     Holders(u64),
 }
 
+/// Signer of the payer: a JSON keypair file or a remote signer
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum SignerConfig {
+    File { payer_keypair: PathBuf },
+    Remote { remote_signer: RemoteSignerConfig },
+}
+
+impl SignerConfig {
+    pub async fn signer(&self) -> anyhow::Result<SharedSigner> {
+        match self {
+            SignerConfig::File { payer_keypair } => Ok(Arc::new(
+                SolanaKeyPayer::read_from_file(payer_keypair).await?,
+            )),
+            SignerConfig::Remote { remote_signer } => Ok(Arc::new(remote_signer.signer()?)),
+        }
+    }
+}
+
+impl Display for SignerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignerConfig::File { payer_keypair } => write!(f, "{}", payer_keypair.display()),
+            SignerConfig::Remote { remote_signer } => {
+                write!(f, "{} at {}", remote_signer.pubkey, remote_signer.url)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PayerConfig {
-    payer_keypair: PathBuf,
+    #[serde(flatten)]
+    signer: SignerConfig,
     fee_recipients: Option<Vec<Address>>,
     number_holders: Option<u64>,
 }
 
 #[derive(Clone, Debug)]
 pub struct Payer {
-    payer_keypair: Arc<Keypair>,
+    signer: SharedSigner,
     resource_type: ResourceType,
 }
 
 impl PayerConfig {
+    pub fn signer(&self) -> &SignerConfig {
+        &self.signer
+    }
+
     pub fn fee_recipients(&self) -> &Option<Vec<Address>> {
         &self.fee_recipients
     }
+
+    pub fn number_holders(&self) -> Option<u64> {
+        self.number_holders
+    }
+
+    /// Checks the payer provides resources: either holders or fee recipients
+    pub fn check_resources(&self) -> anyhow::Result<()> {
+        match (&self.fee_recipients, self.number_holders) {
+            (Some(recipients), None) if !recipients.is_empty() => Ok(()),
+            (None, Some(number)) if number > 0 => Ok(()),
+            _ => Err(anyhow::anyhow!(
+                "Failed to parse payers from config: fee_recipients or holders expected"
+            )),
+        }
+    }
 }
 
 impl Payer {
     pub async fn from_config(cfg: &PayerConfig) -> anyhow::Result<Payer> {
-        let solana_payer = SolanaKeyPayer::read_from_file(&cfg.payer_keypair).await?;
-        if cfg.number_holders.is_none() && cfg.fee_recipients.is_none()
-            || cfg.number_holders.is_some() && cfg.fee_recipients.is_some()
-        {
-            return Err(anyhow::anyhow!(
-                "Failed to parse payers from config: fee_recipients or holders expected"
-            ));
-        }
+        let signer = cfg.signer.signer().await?;
+        cfg.check_resources()?;
 
         let resource_type = if let Some(accs) = cfg.fee_recipients.as_ref() {
             ResourceType::FeeRecipients(accs.clone())
@@ -56,7 +101,7 @@ impl Payer {
         };
 
         Ok(Self {
-            payer_keypair: Arc::new(solana_payer.payer),
+            signer,
             resource_type,
         })
     }
@@ -75,7 +120,7 @@ impl Payer {
 #[allow(dead_code)]
 #[derive(Clone, Debug)]
 struct ResourceItem {
-    payer_keypair: Arc<Keypair>,
+    signer: SharedSigner,
     holder: u64,
     fee_recipient: Option<Address>,
 }
@@ -87,14 +132,14 @@ impl ResourceItem {
                 .into_iter()
                 .enumerate()
                 .map(|(ix, recipient)| ResourceItem {
-                    payer_keypair: payer.payer_keypair.clone(),
+                    signer: payer.signer.clone(),
                     holder: ix as u64,
                     fee_recipient: Some(recipient),
                 })
                 .collect::<Vec<_>>(),
             ResourceType::Holders(number) => (0_u64..number)
                 .map(|holder| Self {
-                    payer_keypair: payer.payer_keypair.clone(),
+                    signer: payer.signer.clone(),
                     holder,
                     fee_recipient: None,
                 })
@@ -150,11 +195,11 @@ impl Resource {
     pub fn holder_index(&self) -> u64 {
         self.item.holder
     }
-    pub fn payer(&self) -> Arc<Keypair> {
-        self.item.payer_keypair.clone()
+    pub fn payer(&self) -> SharedSigner {
+        self.item.signer.clone()
     }
     pub fn payer_key(&self) -> Pubkey {
-        self.item.payer_keypair.pubkey()
+        self.item.signer.pubkey()
     }
     pub fn fee_recipient(&self) -> Vec<u8> {
         if self.item.fee_recipient.is_none() {
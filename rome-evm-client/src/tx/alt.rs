@@ -2,17 +2,18 @@ use {
     super::builder::TxBuilder,
     crate::{
         error::{ProgramResult, RomeEvmError, RomeEvmError::AddressLookupTableNotFound},
+        util::compute_usage,
         Resource,
     },
     async_trait::async_trait,
     emulator::get_alt,
     rome_solana::batch::{AdvanceTx, IxExecStepBatch, OwnedAtomicIxBatch, TxVersion},
+    rome_solana::signer::SharedSigner,
     rome_utils::iter::into_chunks,
     solana_sdk::{
         account::Account,
         address_lookup_table::{state::AddressLookupTable, AddressLookupTableAccount},
         pubkey::Pubkey,
-        signature::Keypair,
     },
     std::sync::Arc,
 };
@@ -91,48 +92,56 @@ impl AltTx {
         data
     }
 
-    pub fn ixs_alloc(&mut self) -> ProgramResult<Vec<OwnedAtomicIxBatch>> {
+    pub async fn ixs_alloc(&mut self) -> ProgramResult<Vec<OwnedAtomicIxBatch>> {
         let data_vec = self.tx_data_alloc();
         let emulation = self
             .tx_builder
-            .emulate(data_vec.last().unwrap(), &self.resource.payer_key())?;
+            .emulate(data_vec.last().unwrap(), &self.resource.payer_key())
+            .await?;
 
+        let usage = compute_usage(&emulation);
         let ixs = data_vec
             .into_iter()
             .map(|x| {
                 let ix = self.tx_builder.build_ix(&emulation, x);
-                OwnedAtomicIxBatch::new_owned(vec![ix])
+                OwnedAtomicIxBatch::new_owned(vec![ix]).with_usage(usage)
             })
             .collect();
 
         Ok(ixs)
     }
 
-    pub fn ix_dealloc(&self) -> ProgramResult<OwnedAtomicIxBatch> {
+    pub async fn ix_dealloc(&self) -> ProgramResult<OwnedAtomicIxBatch> {
         let data = self.tx_data_dealloc();
-        let emulation = self.tx_builder.emulate(&data, &self.resource.payer_key())?;
+        let emulation = self
+            .tx_builder
+            .emulate(&data, &self.resource.payer_key())
+            .await?;
 
         let ix = self.tx_builder.build_ix(&emulation, data);
 
-        Ok(OwnedAtomicIxBatch::new_composible_owned(ix))
+        Ok(OwnedAtomicIxBatch::new_composible_owned(ix).with_usage(compute_usage(&emulation)))
     }
 
-    fn alt_raw_account(&self) -> ProgramResult<(Pubkey, Account)> {
+    async fn alt_raw_account(&self) -> ProgramResult<(Pubkey, Account)> {
         let mut data = vec![];
         data.extend(self.resource.holder());
         data.extend(self.tx_builder.chain_id.to_le_bytes());
 
-        let key = get_alt(
-            self.tx_builder.program_id(),
-            &data,
-            &self.resource.payer_key(),
-            self.tx_builder.client_cloned(),
-        )?
-        .ok_or(AddressLookupTableNotFound)?;
+        let program_id = *self.tx_builder.program_id();
+        let payer = self.resource.payer_key();
+        let client = self.tx_builder.client_cloned();
 
-        let acc = self.tx_builder.client_cloned().get_account(&key)?;
+        self.tx_builder
+            .emulator_pool()
+            .run(move || {
+                let key = get_alt(&program_id, &data, &payer, client.clone())?
+                    .ok_or(AddressLookupTableNotFound)?;
+                let acc = client.get_account(&key)?;
 
-        Ok((key, acc))
+                Ok((key, acc))
+            })
+            .await
     }
     pub fn alt_account_(&self) -> ProgramResult<AddressLookupTableAccount> {
         let (key, acc) = self.alt_raw_account.as_ref().expect("alt_account expected");
@@ -161,11 +170,11 @@ impl AltTx {
 impl AdvanceTx<'_> for AltTx {
     type Error = RomeEvmError;
 
-    fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
+    async fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
         match &mut self.step {
             Steps::Execute => {
-                let mut ixs = vec![self.ix_dealloc()?];
-                let mut alloc = self.ixs_alloc()?;
+                let mut ixs = vec![self.ix_dealloc().await?];
+                let mut alloc = self.ixs_alloc().await?;
                 ixs.append(&mut alloc);
 
                 self.step = Steps::WaitNextSlot;
@@ -175,7 +184,7 @@ impl AdvanceTx<'_> for AltTx {
             Steps::WaitNextSlot => {
                 self.step = Steps::End;
 
-                self.alt_raw_account = Some(self.alt_raw_account()?);
+                self.alt_raw_account = Some(self.alt_raw_account().await?);
                 let slot = self.last_extended_slot()?;
 
                 Ok(IxExecStepBatch::WaitNextSlot(slot))
@@ -183,13 +192,16 @@ impl AdvanceTx<'_> for AltTx {
             _ => Ok(IxExecStepBatch::End),
         }
     }
-    fn advance_with_version(
+    async fn advance_with_version(
         &mut self,
         _: TxVersion,
     ) -> Result<IxExecStepBatch<'static>, Self::Error> {
         unreachable!()
     }
-    fn payer(&self) -> Arc<Keypair> {
+    fn payer(&self) -> SharedSigner {
         self.resource.payer()
     }
+    fn created_alt(&self) -> Option<Pubkey> {
+        self.alt_raw_account.as_ref().map(|(key, _)| *key)
+    }
 }
@@ -3,8 +3,8 @@ use {
     crate::error::{ProgramResult, RomeEvmError},
     async_trait::async_trait,
     rome_solana::batch::{AdvanceTx, IxExecStepBatch, TxVersion},
-    solana_sdk::signature::Keypair,
-    std::sync::Arc,
+    rome_solana::signer::SharedSigner,
+    solana_sdk::pubkey::Pubkey,
 };
 
 enum Steps {
@@ -36,33 +36,36 @@ impl AltComposedHolder {
 impl AdvanceTx<'_> for AltComposedHolder {
     type Error = RomeEvmError;
 
-    fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
+    async fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
         match &mut self.step {
             Steps::AltAndTransmit => {
-                let alt_ix = self.alt_tx.advance()?;
-                let transmit_ix = self.transmit_tx.advance()?;
+                let alt_ix = self.alt_tx.advance().await?;
+                let transmit_ix = self.transmit_tx.advance().await?;
                 let ix = join_parallel(alt_ix, transmit_ix)?;
 
                 if let IxExecStepBatch::End = ix {
                     let alt_account = self.alt_tx.alt_account_()?;
                     self.step = Steps::Execute(TxVersion::V0(vec![alt_account]));
-                    self.advance()
+                    self.advance().await
                 } else {
                     Ok(ix)
                 }
             }
-            Steps::Execute(version) => self.iterable_tx.advance_with_version(version.clone()),
+            Steps::Execute(version) => self.iterable_tx.advance_with_version(version.clone()).await,
         }
     }
-    fn advance_with_version(
+    async fn advance_with_version(
         &mut self,
         _: TxVersion,
     ) -> Result<IxExecStepBatch<'static>, Self::Error> {
         unreachable!()
     }
-    fn payer(&self) -> Arc<Keypair> {
+    fn payer(&self) -> SharedSigner {
         self.iterable_tx.payer()
     }
+    fn created_alt(&self) -> Option<Pubkey> {
+        self.alt_tx.created_alt()
+    }
 }
 
 pub fn join_parallel(
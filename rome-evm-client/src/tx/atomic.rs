@@ -2,12 +2,13 @@ use {
     super::builder::TxBuilder,
     crate::{
         error::{ProgramResult, RomeEvmError},
+        util::compute_usage,
         Resource,
     },
     async_trait::async_trait,
     emulator::Emulation,
     rome_solana::batch::{AdvanceTx, IxExecStepBatch, OwnedAtomicIxBatch, TxVersion},
-    solana_sdk::signature::Keypair,
+    rome_solana::signer::SharedSigner,
     std::sync::Arc,
 };
 
@@ -46,42 +47,46 @@ impl AtomicTx {
         data
     }
 
-    pub fn ix(&mut self) -> ProgramResult<()> {
+    pub async fn ix(&mut self) -> ProgramResult<()> {
         let data = self.tx_data();
-        self.emulation = Some(self.tx_builder.emulate(&data, &self.resource.payer_key())?);
-
-        let ix = self
+        let emulation = self
             .tx_builder
-            .build_ix(self.emulation.as_ref().unwrap(), data);
-        self.ix = Some(OwnedAtomicIxBatch::new_composible_owned(ix));
+            .emulate(&data, &self.resource.payer_key())
+            .await?;
+        self.set_emulation(emulation, data);
 
         Ok(())
     }
+
+    fn set_emulation(&mut self, emulation: Emulation, data: Vec<u8>) {
+        let ix = self.tx_builder.build_ix(&emulation, data);
+        self.ix = Some(
+            OwnedAtomicIxBatch::new_composible_owned(ix).with_usage(compute_usage(&emulation)),
+        );
+        self.emulation = Some(emulation);
+    }
 }
 
-#[macro_export]
-macro_rules! advance_with_version {
-    {} => {
-        fn advance_with_version(&mut self, version: TxVersion) -> Result<IxExecStepBatch<'static>, Self::Error> {
-            let ix = self.advance();
-            match ix {
-                Ok(IxExecStepBatch::Single(ix_, _)) => Ok(IxExecStepBatch::Single(ix_, version)),
-                _ => ix,
-            }
-        }
+// sets the version of the transaction of a single step
+pub(crate) fn single_with_version(
+    batch: ProgramResult<IxExecStepBatch<'static>>,
+    version: TxVersion,
+) -> ProgramResult<IxExecStepBatch<'static>> {
+    match batch {
+        Ok(IxExecStepBatch::Single(ix, _)) => Ok(IxExecStepBatch::Single(ix, version)),
+        _ => batch,
     }
 }
-pub(crate) use advance_with_version;
 
 #[async_trait]
 impl AdvanceTx<'_> for AtomicTx {
     type Error = RomeEvmError;
 
-    fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
+    async fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
         match &mut self.step {
             Steps::Execute => {
                 if self.ix.is_none() {
-                    self.ix()?;
+                    self.ix().await?;
                 }
                 let ix = self.ix.take().unwrap();
 
@@ -91,8 +96,20 @@ impl AdvanceTx<'_> for AtomicTx {
             _ => Ok(IxExecStepBatch::End),
         }
     }
-    advance_with_version!();
-    fn payer(&self) -> Arc<Keypair> {
+    async fn advance_with_version(
+        &mut self,
+        version: TxVersion,
+    ) -> ProgramResult<IxExecStepBatch<'static>> {
+        let batch = self.advance().await;
+        single_with_version(batch, version)
+    }
+    fn payer(&self) -> SharedSigner {
         self.resource.payer()
     }
+    fn remaining_steps(&self) -> Option<usize> {
+        match self.step {
+            Steps::Execute => Some(1),
+            Steps::End => Some(0),
+        }
+    }
 }
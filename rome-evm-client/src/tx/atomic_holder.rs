@@ -1,10 +1,12 @@
 use {
-    super::{atomic::advance_with_version, TransmitTx},
-    crate::error::{ProgramResult, RomeEvmError},
+    super::{atomic::single_with_version, TransmitTx},
+    crate::{
+        error::{ProgramResult, RomeEvmError},
+        util::compute_usage,
+    },
     async_trait::async_trait,
     rome_solana::batch::{AdvanceTx, IxExecStepBatch, OwnedAtomicIxBatch, TxVersion},
-    solana_sdk::signature::Keypair,
-    std::sync::Arc,
+    rome_solana::signer::SharedSigner,
 };
 
 enum Steps {
@@ -37,15 +39,16 @@ impl AtomicTxHolder {
 
         data
     }
-    fn ixs(&self) -> ProgramResult<OwnedAtomicIxBatch> {
+    async fn ixs(&self) -> ProgramResult<OwnedAtomicIxBatch> {
         let data = self.tx_data();
         let emulation = self
             .transmit_tx
             .tx_builder
-            .emulate(&data, &self.transmit_tx.resource.payer_key())?;
+            .emulate(&data, &self.transmit_tx.resource.payer_key())
+            .await?;
         let ix = self.transmit_tx.tx_builder.build_ix(&emulation, data);
 
-        Ok(OwnedAtomicIxBatch::new_composible_owned(ix))
+        Ok(OwnedAtomicIxBatch::new_composible_owned(ix).with_usage(compute_usage(&emulation)))
     }
 }
 
@@ -53,29 +56,42 @@ impl AtomicTxHolder {
 impl AdvanceTx<'_> for AtomicTxHolder {
     type Error = RomeEvmError;
 
-    fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
+    async fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
         match &mut self.step {
             Steps::Transmit => {
-                let ix = self.transmit_tx.advance();
+                let ix = self.transmit_tx.advance().await;
 
                 if let Ok(IxExecStepBatch::End) = ix {
                     self.step = Steps::Execute;
-                    self.advance()
+                    self.advance().await
                 } else {
                     ix
                 }
             }
             Steps::Execute => {
                 self.step = Steps::End;
-                let ix = self.ixs()?;
+                let ix = self.ixs().await?;
 
                 Ok(IxExecStepBatch::Single(ix, TxVersion::Legacy))
             }
             _ => Ok(IxExecStepBatch::End),
         }
     }
-    advance_with_version!();
-    fn payer(&self) -> Arc<Keypair> {
+    async fn advance_with_version(
+        &mut self,
+        version: TxVersion,
+    ) -> ProgramResult<IxExecStepBatch<'static>> {
+        let batch = self.advance().await;
+        single_with_version(batch, version)
+    }
+    fn payer(&self) -> SharedSigner {
         self.transmit_tx.payer()
     }
+    fn remaining_steps(&self) -> Option<usize> {
+        match self.step {
+            Steps::Transmit => self.transmit_tx.remaining_steps().map(|steps| steps + 1),
+            Steps::Execute => Some(1),
+            Steps::End => Some(0),
+        }
+    }
 }
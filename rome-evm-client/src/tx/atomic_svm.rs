@@ -4,11 +4,11 @@ use {
     async_trait::async_trait,
     emulator::Emulation,
     rome_solana::batch::{AdvanceTx, IxExecStepBatch, OwnedAtomicIxBatch, TxVersion},
+    rome_solana::signer::SharedSigner,
     solana_sdk::{
-        signature::Keypair, pubkey::Pubkey, instruction::Instruction, 
+        pubkey::Pubkey, instruction::Instruction, 
         address_lookup_table::AddressLookupTableAccount
     },
-    std::sync::Arc,
 };
 
 enum Steps {
@@ -23,13 +23,13 @@ pub struct AtomicSvm {
 }
 
 impl AtomicSvm {
-    pub fn new(
+    pub async fn new(
         mut atomic_tx: AtomicTx, 
         svm: Vec<Instruction>, 
         alt_keys: Option<Vec<Pubkey>>
     ) -> ProgramResult<Self> {
     
-        atomic_tx.ix()?;
+        atomic_tx.ix().await?;
         let ix = atomic_tx.ix.as_mut().unwrap();
         ix.push(svm);
 
@@ -67,29 +67,36 @@ impl AtomicSvm {
 impl AdvanceTx<'_> for AtomicSvm {
     type Error = RomeEvmError;
 
-    fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
+    async fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
         match &mut self.step {
             Steps::Execute => {
                 self.step = Steps::End;
 
                 if let Some(alt) = self.alts.take() {
-                    self.atomic_tx.advance_with_version(TxVersion::V0(alt))
+                    self.atomic_tx.advance_with_version(TxVersion::V0(alt)).await
                 } else {
-                    self.atomic_tx.advance()
+                    self.atomic_tx.advance().await
                 }
             }
             _ => Ok(IxExecStepBatch::End),
         }
     }
 
-    fn advance_with_version(
+    async fn advance_with_version(
         &mut self,
         _: TxVersion,
     ) -> Result<IxExecStepBatch<'static>, Self::Error> {
         unreachable!()
     }
     
-    fn payer(&self) -> Arc<Keypair> {
+    fn payer(&self) -> SharedSigner {
         self.atomic_tx.resource.payer()
     }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        match self.step {
+            Steps::Execute => Some(1),
+            Steps::End => Some(0),
+        }
+    }
 }
@@ -2,29 +2,28 @@ use {
     crate::{
         error::{ProgramResult, RomeEvmError::{self, *}},
         tx::{
-            AltComposed, AltComposedHolder, AltTx, AtomicTx, AtomicTxHolder, IterativeTx,
+            AltComposed, AltComposedHolder, AltTx, AtomicTx, AtomicTxHolder, CrossChainTx, IterativeTx,
             IterativeTxHolder, TransmitTx, AtomicSvm,
         },
         util::{check_accounts_len, check_exit_reason},
-        Payer, Resource, ResourceFactory,
+        EmulatorPool, Payer, Resource, ResourceFactory,
     },
     bincode::serialize,
     emulator::{emulate, Emulation},
     ethers::types::{Bytes, TxHash},
     rome_solana::{
         batch::{AdvanceTx, OwnedAtomicIxBatch},
+        signer::SharedSigner,
         types::SyncAtomicRpcClient,
     },
     serde_json::json,
     solana_client::rpc_config::RpcSendTransactionConfig,
     solana_program::{
         address_lookup_table::AddressLookupTableAccount,
-        hash::Hash,
         instruction::{AccountMeta, Instruction},
     },
     solana_sdk::{
         bs58, commitment_config::CommitmentLevel, packet::PACKET_DATA_SIZE, pubkey::Pubkey,
-        signer::keypair::Keypair,
     },
     solana_transaction_status::UiTransactionEncoding,
     std::sync::Arc,
@@ -44,6 +43,8 @@ pub struct TxBuilder {
     rpc_client: SyncAtomicRpcClient,
     /// Resource factory to get Solana payer, fee_recipient, holder index
     resource_factory: ResourceFactory,
+    /// Pool running the emulations
+    emulator_pool: EmulatorPool,
 }
 
 impl TxBuilder {
@@ -61,9 +62,21 @@ impl TxBuilder {
             program_id,
             rpc_client,
             resource_factory,
+            emulator_pool: EmulatorPool::default(),
         }
     }
 
+    /// Run the emulations on the given [EmulatorPool]
+    pub fn with_emulator_pool(mut self, emulator_pool: EmulatorPool) -> Self {
+        self.emulator_pool = emulator_pool;
+        self
+    }
+
+    /// get emulator pool
+    pub fn emulator_pool(&self) -> &EmulatorPool {
+        &self.emulator_pool
+    }
+
     pub async fn lock_resource(&self) -> ProgramResult<Arc<Resource>> {
         let resource = self.resource_factory.get().await?;
         Ok(Arc::new(resource))
@@ -74,12 +87,17 @@ impl TxBuilder {
         &self.program_id
     }
 
-    /// Emulate a transaction
+    /// Emulate a transaction on the [EmulatorPool]
     #[tracing::instrument(skip(self, data))]
-    pub fn emulate(&self, data: &[u8], payer: &Pubkey) -> ProgramResult<Emulation> {
+    pub async fn emulate(&self, data: &[u8], payer: &Pubkey) -> ProgramResult<Emulation> {
         tracing::info!("Emulating Transaction with payer: {:?}", payer);
 
-        let emulation = emulate(&self.program_id, data, payer, self.rpc_client.clone())?;
+        let (program_id, data, payer) = (self.program_id, data.to_vec(), *payer);
+        let rpc_client = self.rpc_client.clone();
+        let emulation = self
+            .emulator_pool
+            .run(move || Ok(emulate(&program_id, &data, &payer, rpc_client)?))
+            .await?;
         check_exit_reason(&emulation)?;
         check_accounts_len(&emulation)?;
 
@@ -123,6 +141,23 @@ impl TxBuilder {
         }
     }
 
+    /// Compose a cross chain transaction signed by the additional signers.
+    /// Sent as a v0 transaction with an address lookup table if the accounts
+    /// do not fit into a legacy transaction.
+    pub fn compose_cross_chain_tx(
+        &self,
+        ixs: OwnedAtomicIxBatch,
+        resource: Arc<Resource>,
+        signers: Vec<SharedSigner>,
+    ) -> ProgramResult<Iterable> {
+        let mut keys = tx_keys(&ixs);
+        keys.sort();
+        keys.dedup();
+
+        let tx = Box::new(CrossChainTx::new(ixs, resource.payer(), signers));
+        self.compose_iterable_without_holder(use_alt(&keys), keys, resource, tx)
+    }
+
     pub fn compose_iterable(
         &self,
         ix: &OwnedAtomicIxBatch,
@@ -147,7 +182,7 @@ impl TxBuilder {
 
         if use_holder(
             ix,
-            &resource.payer(),
+            &resource.payer_key(),
             self.rpc_client.commitment().commitment,
             alt.as_ref(),
         )? {
@@ -171,7 +206,7 @@ impl TxBuilder {
         let resource = self.lock_resource().await?;
         let mut atomic_tx = AtomicTx::new(self.clone(), rlp.to_vec(), resource.clone());
         // Build the instruction
-        atomic_tx.ix()?;
+        atomic_tx.ix().await?;
 
         let emulation = atomic_tx.emulation.as_ref().unwrap();
 
@@ -184,7 +219,7 @@ impl TxBuilder {
             tracing::info!("Building iterative transaction");
 
             let mut iterative_tx = IterativeTx::new(self.clone(), resource.clone(), rlp.clone())?;
-            iterative_tx.ixs()?;
+            iterative_tx.ixs().await?;
 
             let ix = iterative_tx
                 .ixs
@@ -200,6 +235,43 @@ impl TxBuilder {
         self.compose_iterable(&ix, resource, rlp, hash, tx, is_atomic)
     }
 
+    /// Build the instruction depositing SOLs of the signer to a rollup account,
+    /// see [RomeEVMClient::deposit](crate::RomeEVMClient::deposit)
+    ///
+    /// * `rlp` - deposit transaction (type 0x7E)
+    /// * `signer` - owner of the deposited SOLs
+    pub async fn build_deposit_ix(
+        &self,
+        rlp: &[u8],
+        signer: &Pubkey,
+    ) -> ProgramResult<Instruction> {
+        let mut data = vec![emulator::Instruction::Deposit as u8];
+        data.extend(self.chain_id.to_le_bytes());
+        data.extend(rlp);
+
+        let emulation = self.emulate(&data, signer).await?;
+
+        Ok(self.build_ix(&emulation, data))
+    }
+
+    /// Build the instruction registering the owner of a rollup,
+    /// signed by the registry authority of the Rome-EVM program
+    ///
+    /// * `chain_id` - chain id of the rollup
+    /// * `registry_authority` - registry authority of the Rome-EVM program
+    pub async fn build_reg_owner_ix(
+        &self,
+        chain_id: u64,
+        registry_authority: &Pubkey,
+    ) -> ProgramResult<Instruction> {
+        let mut data = vec![emulator::Instruction::RegOwner as u8];
+        data.extend(chain_id.to_le_bytes());
+
+        let emulation = self.emulate(&data, registry_authority).await?;
+
+        Ok(self.build_ix(&emulation, data))
+    }
+
     /// Build a Solana instruction from [Emulation] and data
     pub fn build_ix(&self, emulation: &Emulation, data: Vec<u8>) -> Instruction {
         let accounts = emulation
@@ -223,22 +295,29 @@ impl TxBuilder {
         self.rpc_client.clone()
     }
 
-    pub fn confirm_tx_iterative(
+    pub async fn confirm_tx_iterative(
         &self,
         holder: u64,
         hash: TxHash,
         payer: &Pubkey,
         session: u64,
     ) -> ProgramResult<bool> {
-        Ok(emulator::confirm_tx_iterative(
-            &self.program_id,
-            holder,
-            rome_evm::H256::from_slice(hash.as_bytes()),
-            payer,
-            self.client_cloned(),
-            self.chain_id,
-            session,
-        )?)
+        let (program_id, payer, client, chain_id) =
+            (self.program_id, *payer, self.client_cloned(), self.chain_id);
+
+        self.emulator_pool
+            .run(move || {
+                Ok(emulator::confirm_tx_iterative(
+                    &program_id,
+                    holder,
+                    rome_evm::H256::from_slice(hash.as_bytes()),
+                    &payer,
+                    client,
+                    chain_id,
+                    session,
+                )?)
+            })
+            .await
     }
 
     /// Build a composite transaction consisting of rome-evm instruction and SVM-instructions
@@ -251,7 +330,7 @@ impl TxBuilder {
     ) -> ProgramResult<Iterable> {
         let resource = self.lock_resource().await?;
         let atomic_tx = AtomicTx::new(self.clone(), rlp.to_vec(), resource.clone());
-        let atomic_svm = AtomicSvm::new(atomic_tx, svm, alt_keys)?;
+        let atomic_svm = AtomicSvm::new(atomic_tx, svm, alt_keys).await?;
         let ix = atomic_svm.ix();
         let alts = atomic_svm.alts.as_ref();
         let emulation = atomic_svm.emulation();
@@ -263,7 +342,7 @@ impl TxBuilder {
         
         if use_holder(
             ix,
-            &resource.payer(),
+            &resource.payer_key(),
             self.rpc_client.commitment().commitment,
             alts
         )? {
@@ -276,7 +355,7 @@ impl TxBuilder {
 
 fn use_holder(
     ix: &OwnedAtomicIxBatch,
-    payer: &Keypair,
+    payer: &Pubkey,
     level: CommitmentLevel,
     alts: Option<&Vec<AddressLookupTableAccount>>,
 ) -> ProgramResult<bool> {
@@ -286,11 +365,7 @@ fn use_holder(
         return Ok(true);
     }
 
-    let tx = if let Some(alts_) = alts {
-        ix.compose_v0_solana_tx(payer, Hash::default(), alts_)?
-    } else {
-        ix.compose_legacy_solana_tx(payer, Hash::default())
-    };
+    let tx = ix.compose_unsigned_solana_tx(payer, alts.map(Vec::as_slice).unwrap_or_default())?;
 
     let json = serialize_encode(&tx, UiTransactionEncoding::Base64, level)?;
     // subtract the additional 100 bytes to build json:
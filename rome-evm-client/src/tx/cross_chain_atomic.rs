@@ -1,20 +1,19 @@
 use crate::error::{ProgramResult, RomeEvmError};
 use rome_solana::batch::{AdvanceTx, AtomicIxBatch, IxExecStepBatch, TxVersion};
-use solana_sdk::signature::Keypair;
-use std::sync::Arc;
+use rome_solana::signer::SharedSigner;
 
 pub struct CrossChainTx {
     instructions: AtomicIxBatch<'static>,
     complete: bool,
-    payer: Arc<Keypair>,
-    signers: Vec<Arc<Keypair>>,
+    payer: SharedSigner,
+    signers: Vec<SharedSigner>,
 }
 
 impl CrossChainTx {
     pub fn new(
         instructions: AtomicIxBatch<'static>,
-        payer: Arc<Keypair>,
-        signers: Vec<Arc<Keypair>>,
+        payer: SharedSigner,
+        signers: Vec<SharedSigner>,
     ) -> Self {
         Self {
             instructions,
@@ -23,31 +22,32 @@ impl CrossChainTx {
             signers,
         }
     }
-}
-
-#[async_trait::async_trait]
-impl<'a> AdvanceTx<'a> for CrossChainTx {
-    type Error = RomeEvmError;
 
-    fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'a>> {
+    fn step<'a>(&mut self, version: TxVersion) -> IxExecStepBatch<'a> {
         if self.complete {
-            return Ok(IxExecStepBatch::End);
+            return IxExecStepBatch::End;
         }
 
         self.complete = true;
 
-        Ok(IxExecStepBatch::SingleWithSigners(
-            self.instructions.clone(),
-            self.signers.clone(),
-        ))
+        IxExecStepBatch::SingleWithSigners(self.instructions.clone(), self.signers.clone(), version)
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> AdvanceTx<'a> for CrossChainTx {
+    type Error = RomeEvmError;
+
+    async fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'a>> {
+        Ok(self.step(TxVersion::Legacy))
     }
-    fn advance_with_version(
+    async fn advance_with_version(
         &mut self,
-        _: TxVersion,
+        version: TxVersion,
     ) -> Result<IxExecStepBatch<'static>, Self::Error> {
-        unreachable!()
+        Ok(self.step(version))
     }
-    fn payer(&self) -> Arc<Keypair> {
+    fn payer(&self) -> SharedSigner {
         self.payer.clone()
     }
 }
@@ -1,16 +1,15 @@
 use crate::error::{ProgramResult, RomeEvmError};
 use rome_solana::batch::{AdvanceTx, AtomicIxBatch, IxExecStepBatch, TxVersion};
-use solana_sdk::signature::Keypair;
-use std::sync::Arc;
+use rome_solana::signer::SharedSigner;
 
 pub struct CrossRollupTx {
     instructions: AtomicIxBatch<'static>,
     complete: bool,
-    payer: Arc<Keypair>,
+    payer: SharedSigner,
 }
 
 impl CrossRollupTx {
-    pub fn new(instructions: AtomicIxBatch<'static>, payer: Arc<Keypair>) -> Self {
+    pub fn new(instructions: AtomicIxBatch<'static>, payer: SharedSigner) -> Self {
         Self {
             instructions,
             complete: false,
@@ -23,7 +22,7 @@ impl CrossRollupTx {
 impl<'a> AdvanceTx<'a> for CrossRollupTx {
     type Error = RomeEvmError;
 
-    fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'a>> {
+    async fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'a>> {
         if self.complete {
             return Ok(IxExecStepBatch::End);
         }
@@ -36,13 +35,13 @@ impl<'a> AdvanceTx<'a> for CrossRollupTx {
         ))
     }
 
-    fn advance_with_version(
+    async fn advance_with_version(
         &mut self,
         _: TxVersion,
     ) -> Result<IxExecStepBatch<'static>, Self::Error> {
         unreachable!()
     }
-    fn payer(&self) -> Arc<Keypair> {
+    fn payer(&self) -> SharedSigner {
         self.payer.clone()
     }
 }
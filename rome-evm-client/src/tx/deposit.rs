@@ -0,0 +1,331 @@
+use crate::error::{ProgramResult, RomeEvmError};
+use crate::indexer::parsers::l1_attributes::l1_block_number;
+use crate::indexer::{BlockType, EthereumBlockStorage};
+use async_trait::async_trait;
+use ethers::providers::Middleware;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::transaction::optimism::DepositTransaction;
+use ethers::types::{Address, Bytes, Transaction, TransactionRequest, TxHash, H256, U256, U64};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::clock::Slot;
+use solana_sdk::signature::Signature;
+use std::time::Duration;
+
+/// Number of wei in a lamport: precision of rome-evm token is 10^18, precision of SOL is 10^9
+pub const WEI_PER_LAMPORT: u64 = 1_000_000_000;
+
+/// Gas limit of a deposit, deposits do not call contracts
+const DEPOSIT_GAS_LIMIT: u64 = 21_000;
+
+const DEPOSIT_POLL_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Amount of a deposit, 1 SOL = 1 rome-evm token
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepositAmount {
+    Lamports(u64),
+
+    /// Must be a multiple of [WEI_PER_LAMPORT]
+    Wei(U256),
+}
+
+impl DepositAmount {
+    /// Amount in wei minted on the recipient account
+    pub fn to_wei(&self) -> ProgramResult<U256> {
+        match self {
+            Self::Lamports(lamports) => Ok(U256::from(*lamports) * U256::from(WEI_PER_LAMPORT)),
+            Self::Wei(wei) if (wei % U256::from(WEI_PER_LAMPORT)).is_zero() => Ok(*wei),
+            Self::Wei(wei) => Err(RomeEvmError::InvalidDepositAmount(*wei)),
+        }
+    }
+}
+
+/// Builds the deposit transaction (type 0x7E) minting the amount on the recipient account
+///
+/// The source hash is random to make every deposit unique, the indexer replaces it
+/// with the one derived from the Solana block.
+pub fn build_deposit_rlp(recipient: Address, amount: DepositAmount) -> ProgramResult<Bytes> {
+    let tx = TypedTransaction::DepositTransaction(DepositTransaction {
+        tx: TransactionRequest {
+            from: Some(recipient),
+            to: Some(recipient.into()),
+            gas: Some(U256::from(DEPOSIT_GAS_LIMIT)),
+            ..Default::default()
+        },
+        source_hash: H256::random(),
+        mint: Some(amount.to_wei()?),
+        is_system_tx: false,
+    });
+
+    Ok(tx.rlp())
+}
+
+/// Whether the transaction is the deposit minting the amount in wei on the recipient account
+pub fn is_deposit(tx: &Transaction, recipient: Address, mint: U256) -> bool {
+    tx.transaction_type == Some(U64::from(0x7E))
+        && !tx.is_system_tx
+        && tx.from == recipient
+        && tx.to == Some(recipient)
+        && tx.mint == Some(mint)
+}
+
+/// Returns the Solana slot the deposit transaction is confirmed in
+pub async fn deposit_slot(client: &RpcClient, signature: Signature) -> ProgramResult<Slot> {
+    Ok(client
+        .get_signature_statuses(&[signature])
+        .await?
+        .value
+        .into_iter()
+        .flatten()
+        .next()
+        .ok_or_else(|| RomeEvmError::Custom(format!("Status of deposit {} not found", signature)))?
+        .slot)
+}
+
+/// Rollup blocks the deposits are looked for in
+#[async_trait]
+pub(crate) trait DepositBlocks {
+    async fn latest_block(&self) -> ProgramResult<U64>;
+
+    // Solana slot of the block if known and its transactions
+    async fn block(&self, number: U64) -> ProgramResult<(Option<Slot>, Vec<Transaction>)>;
+}
+
+#[async_trait]
+impl DepositBlocks for dyn EthereumBlockStorage {
+    async fn latest_block(&self) -> ProgramResult<U64> {
+        Ok(EthereumBlockStorage::latest_block(self)
+            .await?
+            .unwrap_or_default())
+    }
+
+    async fn block(&self, number: U64) -> ProgramResult<(Option<Slot>, Vec<Transaction>)> {
+        let slot = self.get_slot_for_eth_block(number).await?;
+        let txs = match self.get_block_by_number(number, true).await? {
+            Some(BlockType::BlockWithTransactions(block)) => block.transactions,
+            _ => vec![],
+        };
+
+        Ok((slot, txs))
+    }
+}
+
+// blocks read from the rollup JSON-RPC, their slot is set by the L1 attributes transaction
+struct RollupBlocks<'a, M>(&'a M);
+
+#[async_trait]
+impl<M: Middleware> DepositBlocks for RollupBlocks<'_, M> {
+    async fn latest_block(&self) -> ProgramResult<U64> {
+        self.0.get_block_number().await.map_err(rpc_error::<M>)
+    }
+
+    async fn block(&self, number: U64) -> ProgramResult<(Option<Slot>, Vec<Transaction>)> {
+        let txs = self
+            .0
+            .get_block_with_txs(number)
+            .await
+            .map_err(rpc_error::<M>)?
+            .map(|block| block.transactions)
+            .unwrap_or_default();
+
+        Ok((txs.first().and_then(l1_block_number), txs))
+    }
+}
+
+fn rpc_error<M: Middleware>(e: M::Error) -> RomeEvmError {
+    RomeEvmError::Custom(format!("Rollup JSON-RPC error: {}", e))
+}
+
+/// Waits until the deposit sent in the Solana slot is included in a block and returns its hash
+///
+/// * `from_block` - first block to look for the deposit in
+/// * `timeout` - maximum time to wait for the blocks
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn wait_deposit<B: DepositBlocks + ?Sized>(
+    blocks: &B,
+    signature: Signature,
+    slot: Slot,
+    from_block: U64,
+    recipient: Address,
+    mint: U256,
+    timeout: Duration,
+) -> ProgramResult<TxHash> {
+    let poll = async {
+        let mut number = from_block;
+        // blocks without the slot belong to the slot of the previous block
+        let mut block_slot = None;
+        loop {
+            let latest = blocks.latest_block().await?;
+            while number <= latest {
+                let (slot_of_block, txs) = blocks.block(number).await?;
+                block_slot = slot_of_block.or(block_slot);
+                match block_slot {
+                    Some(block_slot) if block_slot == slot => {
+                        if let Some(tx) = txs.iter().find(|tx| is_deposit(tx, recipient, mint)) {
+                            return Ok(tx.hash);
+                        }
+                    }
+                    Some(block_slot) if block_slot > slot => {
+                        return Err(RomeEvmError::Custom(format!(
+                            "Deposit {} is not found in the blocks of slot {}",
+                            signature, slot
+                        )));
+                    }
+                    _ => {}
+                }
+
+                number += U64::one();
+            }
+
+            tokio::time::sleep(DEPOSIT_POLL_INTERVAL).await;
+        }
+    };
+
+    tokio::time::timeout(timeout, poll)
+        .await
+        .map_err(|_| RomeEvmError::DepositTimeout(signature))?
+}
+
+/// Waits until the deposit sent in the Solana slot is included in a block of the rollup
+/// and returns its hash
+///
+/// Used without access to the storage of the indexer, the blocks are read from
+/// the rollup JSON-RPC. The rollup must have deposits enabled, its blocks start with
+/// the L1 attributes transaction holding the Solana slot.
+///
+/// * `rollup` - provider of the rollup JSON-RPC
+/// * `slot` - Solana slot of the deposit, see [deposit_slot]
+/// * `from_block` - first block to look for the deposit in, the block following
+///   the latest one before the deposit is sent
+/// * `timeout` - maximum time to wait for the rollup
+pub async fn wait_deposit_included<M: Middleware>(
+    rollup: &M,
+    signature: Signature,
+    slot: Slot,
+    from_block: U64,
+    recipient: Address,
+    mint: U256,
+    timeout: Duration,
+) -> ProgramResult<TxHash> {
+    wait_deposit(
+        &RollupBlocks(rollup),
+        signature,
+        slot,
+        from_block,
+        recipient,
+        mint,
+        timeout,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rlp::{Decodable, Rlp};
+
+    #[test]
+    fn deposit_amount_precision() {
+        assert_eq!(
+            DepositAmount::Lamports(3).to_wei().unwrap(),
+            U256::from(3_000_000_000u64)
+        );
+        assert_eq!(
+            DepositAmount::Wei(U256::from(5_000_000_000u64))
+                .to_wei()
+                .unwrap(),
+            U256::from(5_000_000_000u64)
+        );
+        assert!(matches!(
+            DepositAmount::Wei(U256::from(5_000_000_001u64)).to_wei(),
+            Err(RomeEvmError::InvalidDepositAmount(_))
+        ));
+    }
+
+    #[test]
+    fn build_deposit() {
+        let recipient = Address::random();
+        let rlp = build_deposit_rlp(recipient, DepositAmount::Lamports(7)).unwrap();
+        assert_eq!(rlp[0], 0x7E);
+
+        let tx = Transaction::decode(&Rlp::new(rlp.as_ref())).unwrap();
+        assert!(is_deposit(&tx, recipient, U256::from(7_000_000_000u64)));
+        assert!(!is_deposit(&tx, recipient, U256::from(7u64)));
+        assert!(!is_deposit(
+            &tx,
+            Address::random(),
+            U256::from(7_000_000_000u64)
+        ));
+    }
+
+    struct MockBlocks(Vec<(Option<Slot>, Vec<Transaction>)>);
+
+    #[async_trait]
+    impl DepositBlocks for MockBlocks {
+        async fn latest_block(&self) -> ProgramResult<U64> {
+            Ok(U64::from(self.0.len()))
+        }
+
+        async fn block(&self, number: U64) -> ProgramResult<(Option<Slot>, Vec<Transaction>)> {
+            Ok(self.0[number.as_usize() - 1].clone())
+        }
+    }
+
+    fn deposit(recipient: Address) -> Transaction {
+        let rlp = build_deposit_rlp(recipient, DepositAmount::Lamports(1)).unwrap();
+        let mut tx = Transaction::decode(&Rlp::new(rlp.as_ref())).unwrap();
+        tx.hash = TxHash::random();
+        tx
+    }
+
+    #[tokio::test]
+    async fn deposit_of_the_slot() {
+        let recipient = Address::random();
+        let (first, second) = (deposit(recipient), deposit(recipient));
+        let blocks = MockBlocks(vec![
+            (Some(10), vec![first]),
+            (Some(11), vec![]),
+            // second block of slot 11
+            (None, vec![second.clone()]),
+            (Some(12), vec![]),
+        ]);
+        let mint = U256::from(WEI_PER_LAMPORT);
+        let timeout = Duration::from_secs(1);
+
+        let hash = wait_deposit(
+            &blocks,
+            Signature::default(),
+            11,
+            U64::one(),
+            recipient,
+            mint,
+            timeout,
+        )
+        .await
+        .unwrap();
+        assert_eq!(hash, second.hash);
+
+        // the deposit is not in the blocks of its slot
+        assert!(wait_deposit(
+            &blocks,
+            Signature::default(),
+            11,
+            U64::from(4),
+            recipient,
+            mint,
+            timeout
+        )
+        .await
+        .is_err());
+    }
+
+    #[test]
+    fn slot_of_l1_attributes() {
+        let (tx, _) = crate::indexer::parsers::l1_attributes::create_l1_attributes_tx(
+            42,
+            &H256::random(),
+            &U256::one(),
+        );
+        assert_eq!(l1_block_number(&tx), Some(42));
+        assert_eq!(l1_block_number(&deposit(Address::random())), None);
+    }
+}
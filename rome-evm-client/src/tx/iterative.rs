@@ -2,13 +2,14 @@ use {
     super::{builder::TxBuilder, AltTx},
     crate::{
         error::{ProgramResult, RomeEvmError},
+        util::compute_usage,
         Resource,
     },
     async_trait::async_trait,
     emulator::Emulation,
     ethers::{prelude::TxHash, types::Bytes, utils::keccak256},
     rome_solana::batch::{AdvanceTx, IxExecStepBatch, OwnedAtomicIxBatch, TxVersion},
-    solana_sdk::signature::Keypair,
+    rome_solana::signer::SharedSigner,
     std::sync::Arc,
 };
 
@@ -72,10 +73,17 @@ impl IterativeTx {
         Ok(data)
     }
 
-    pub fn ixs(&mut self) -> ProgramResult<()> {
+    pub async fn ixs(&mut self) -> ProgramResult<()> {
         let data = self.emulation_data();
-        let emulation = self.tx_builder.emulate(&data, &self.resource.payer_key())?;
+        let emulation = self
+            .tx_builder
+            .emulate(&data, &self.resource.payer_key())
+            .await?;
 
+        self.set_emulation(emulation)
+    }
+
+    fn set_emulation(&mut self, emulation: Emulation) -> ProgramResult<()> {
         let vm = emulation.vm.as_ref().expect("vm expected");
         let count = (vm.iteration_count as f64 * MULTIPLE_ITERATIONS) as u64;
 
@@ -86,35 +94,39 @@ impl IterativeTx {
             .map(|data| self.tx_builder.build_ix(&emulation, data))
             .collect();
 
-        self.ixs = Some(OwnedAtomicIxBatch::new_composible_batches_owned(ixs));
+        let usage = compute_usage(&emulation);
+        self.ixs = Some(
+            OwnedAtomicIxBatch::new_composible_batches_owned(ixs)
+                .into_iter()
+                .map(|batch| batch.with_usage(usage))
+                .collect(),
+        );
 
         Ok(())
     }
 }
 
-#[macro_export]
-macro_rules! advance_with_version_it {
-    {} => {
-        fn advance_with_version(&mut self, version: TxVersion) -> Result<IxExecStepBatch<'static>, Self::Error> {
-            let ix = self.advance();
-            match ix {
-                Ok(IxExecStepBatch::ParallelUnchecked(ix_, _)) =>
-                    Ok(IxExecStepBatch::ParallelUnchecked(ix_, version)),
-                _ => ix,
-            }
+// sets the version of the transactions of a parallel unchecked step
+pub(crate) fn parallel_unchecked_with_version(
+    batch: ProgramResult<IxExecStepBatch<'static>>,
+    version: TxVersion,
+) -> ProgramResult<IxExecStepBatch<'static>> {
+    match batch {
+        Ok(IxExecStepBatch::ParallelUnchecked(ixs, _)) => {
+            Ok(IxExecStepBatch::ParallelUnchecked(ixs, version))
         }
+        _ => batch,
     }
 }
-pub(crate) use advance_with_version_it;
 
 #[async_trait]
 impl AdvanceTx<'_> for IterativeTx {
     type Error = RomeEvmError;
-    fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
+    async fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
         match &mut self.step {
             Steps::Execute => {
                 if self.ixs.is_none() {
-                    self.ixs()?;
+                    self.ixs().await?;
                 }
 
                 self.step = Steps::Confirm;
@@ -125,20 +137,36 @@ impl AdvanceTx<'_> for IterativeTx {
             Steps::Confirm => {
                 self.step = Steps::Complete;
 
-                let confirm = self.tx_builder.confirm_tx_iterative(
-                    self.resource.holder_index(),
-                    self.hash,
-                    &self.resource.payer_key(),
-                    self.session,
-                )?;
+                let confirm = self
+                    .tx_builder
+                    .confirm_tx_iterative(
+                        self.resource.holder_index(),
+                        self.hash,
+                        &self.resource.payer_key(),
+                        self.session,
+                    )
+                    .await?;
 
                 Ok(IxExecStepBatch::ConfirmationIterativeTx(confirm))
             }
             _ => Ok(IxExecStepBatch::End),
         }
     }
-    advance_with_version_it!();
-    fn payer(&self) -> Arc<Keypair> {
+    async fn advance_with_version(
+        &mut self,
+        version: TxVersion,
+    ) -> ProgramResult<IxExecStepBatch<'static>> {
+        let batch = self.advance().await;
+        parallel_unchecked_with_version(batch, version)
+    }
+    fn payer(&self) -> SharedSigner {
         self.resource.payer()
     }
+    fn remaining_steps(&self) -> Option<usize> {
+        match self.step {
+            Steps::Execute => Some(2),
+            Steps::Confirm => Some(1),
+            Steps::Complete => Some(0),
+        }
+    }
 }
@@ -1,11 +1,13 @@
 use {
-    super::{iterative::advance_with_version_it, TransmitTx, MULTIPLE_ITERATIONS},
-    crate::error::{ProgramResult, RomeEvmError},
+    super::{iterative::parallel_unchecked_with_version, TransmitTx, MULTIPLE_ITERATIONS},
+    crate::{
+        error::{ProgramResult, RomeEvmError},
+        util::compute_usage,
+    },
     async_trait::async_trait,
     emulator::Emulation,
     rome_solana::batch::{AdvanceTx, IxExecStepBatch, OwnedAtomicIxBatch, TxVersion},
-    solana_sdk::signature::Keypair,
-    std::sync::Arc,
+    rome_solana::signer::SharedSigner,
 };
 
 pub struct IterativeTxHolder {
@@ -60,12 +62,13 @@ impl IterativeTxHolder {
         Ok(data)
     }
 
-    fn ixs(&self) -> ProgramResult<Vec<OwnedAtomicIxBatch>> {
+    async fn ixs(&self) -> ProgramResult<Vec<OwnedAtomicIxBatch>> {
         let data = self.emulation_data();
         let emulation = self
             .transmit_tx
             .tx_builder
-            .emulate(&data, &self.transmit_tx.resource.payer_key())?;
+            .emulate(&data, &self.transmit_tx.resource.payer_key())
+            .await?;
 
         let vm = emulation.vm.as_ref().expect("vm expected");
         let count = (vm.iteration_count as f64 * MULTIPLE_ITERATIONS) as u64;
@@ -77,40 +80,50 @@ impl IterativeTxHolder {
             .map(|data| self.transmit_tx.tx_builder.build_ix(&emulation, data))
             .collect();
 
-        Ok(OwnedAtomicIxBatch::new_composible_batches_owned(ixs))
+        let usage = compute_usage(&emulation);
+        Ok(OwnedAtomicIxBatch::new_composible_batches_owned(ixs)
+            .into_iter()
+            .map(|batch| batch.with_usage(usage))
+            .collect())
     }
 }
 
 #[async_trait]
 impl AdvanceTx<'_> for IterativeTxHolder {
     type Error = RomeEvmError;
-    fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
+    async fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
         match &mut self.step {
             Steps::Transmit => {
-                let ix = self.transmit_tx.advance();
+                let ix = self.transmit_tx.advance().await;
 
                 if let Ok(IxExecStepBatch::End) = ix {
                     self.step = Steps::Execute;
-                    self.advance()
+                    self.advance().await
                 } else {
                     ix
                 }
             }
             Steps::Execute => {
                 self.step = Steps::Confirm;
-                let ixs = self.ixs()?;
+                let ixs = self.ixs().await?;
 
                 Ok(IxExecStepBatch::ParallelUnchecked(ixs, TxVersion::Legacy))
             }
             Steps::Confirm => {
                 self.step = Steps::End;
 
-                match self.transmit_tx.tx_builder.confirm_tx_iterative(
-                    self.transmit_tx.resource.holder_index(),
-                    self.transmit_tx.hash,
-                    &self.transmit_tx.resource.payer_key(),
-                    self.session,
-                ) {
+                let confirm = self
+                    .transmit_tx
+                    .tx_builder
+                    .confirm_tx_iterative(
+                        self.transmit_tx.resource.holder_index(),
+                        self.transmit_tx.hash,
+                        &self.transmit_tx.resource.payer_key(),
+                        self.session,
+                    )
+                    .await;
+
+                match confirm {
                     Ok(confirm) => Ok(IxExecStepBatch::ConfirmationIterativeTx(confirm)),
                     Err(e) => {
                         tracing::error!(
@@ -125,8 +138,22 @@ impl AdvanceTx<'_> for IterativeTxHolder {
             _ => Ok(IxExecStepBatch::End),
         }
     }
-    advance_with_version_it!();
-    fn payer(&self) -> Arc<Keypair> {
+    async fn advance_with_version(
+        &mut self,
+        version: TxVersion,
+    ) -> ProgramResult<IxExecStepBatch<'static>> {
+        let batch = self.advance().await;
+        parallel_unchecked_with_version(batch, version)
+    }
+    fn payer(&self) -> SharedSigner {
         self.transmit_tx.payer()
     }
+    fn remaining_steps(&self) -> Option<usize> {
+        match self.step {
+            Steps::Transmit => self.transmit_tx.remaining_steps().map(|steps| steps + 2),
+            Steps::Execute => Some(2),
+            Steps::Confirm => Some(1),
+            Steps::End => Some(0),
+        }
+    }
 }
@@ -6,9 +6,12 @@ mod atomic_holder;
 mod builder;
 mod cross_chain_atomic;
 mod cross_rollup_atomic;
+mod deposit;
 mod iterative;
 mod iterative_holder;
+mod sent_tx;
 pub mod transmit_tx;
+mod tx_status;
 mod atomic_svm;
 
 pub use alt::*;
@@ -19,7 +22,10 @@ pub use atomic_holder::*;
 pub use builder::*;
 pub use cross_chain_atomic::*;
 pub use cross_rollup_atomic::*;
+pub use deposit::*;
 pub use iterative::*;
 pub use iterative_holder::*;
+pub use sent_tx::*;
 pub use transmit_tx::*;
+pub use tx_status::*;
 pub use atomic_svm::*;
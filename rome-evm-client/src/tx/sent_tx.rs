@@ -0,0 +1,65 @@
+use crate::error::{ProgramResult, RomeEvmError};
+use crate::indexer::EthereumBlockStorage;
+use ethers::types::{TransactionReceipt, TxHash};
+use solana_sdk::signature::Signature;
+use std::sync::Arc;
+use std::time::Duration;
+
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Handle of a transaction executed on the rollup
+///
+/// Maps the Ethereum transaction to the Solana transactions which executed it
+/// and waits for the receipt produced by the indexer.
+pub struct SentTx {
+    hash: TxHash,
+    signatures: Vec<Signature>,
+    ethereum_block_storage: Arc<dyn EthereumBlockStorage>,
+}
+
+impl SentTx {
+    pub fn new(
+        hash: TxHash,
+        signatures: Vec<Signature>,
+        ethereum_block_storage: Arc<dyn EthereumBlockStorage>,
+    ) -> Self {
+        Self {
+            hash,
+            signatures,
+            ethereum_block_storage,
+        }
+    }
+
+    /// Ethereum transaction hash
+    pub fn hash(&self) -> TxHash {
+        self.hash
+    }
+
+    /// Signatures of all Solana transactions sent to execute the transaction, in order
+    pub fn signatures(&self) -> &[Signature] {
+        &self.signatures
+    }
+
+    /// Waits until the transaction is indexed and returns its receipt
+    ///
+    /// * `timeout` - maximum time to wait for the indexer
+    pub async fn receipt(&self, timeout: Duration) -> ProgramResult<TransactionReceipt> {
+        let poll = async {
+            loop {
+                if let Some(receipt) = self
+                    .ethereum_block_storage
+                    .get_transaction_receipt(&self.hash)
+                    .await?
+                {
+                    return Ok(receipt);
+                }
+
+                tokio::time::sleep(RECEIPT_POLL_INTERVAL).await;
+            }
+        };
+
+        tokio::time::timeout(timeout, poll)
+            .await
+            .map_err(|_| RomeEvmError::ReceiptTimeout(self.hash))?
+    }
+}
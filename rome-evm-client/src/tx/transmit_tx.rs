@@ -2,14 +2,15 @@ use {
     super::builder::TxBuilder,
     crate::{
         error::{ProgramResult, RomeEvmError},
+        util::compute_usage,
         Resource,
     },
     async_trait::async_trait,
     ethers::types::{Bytes, TxHash},
     rome_solana::batch::{AdvanceTx, IxExecStepBatch, OwnedAtomicIxBatch, TxVersion},
+    rome_solana::signer::SharedSigner,
     rome_utils::iter::into_chunks,
     solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE,
-    solana_sdk::signature::Keypair,
     std::sync::Arc,
 };
 
@@ -53,10 +54,14 @@ impl TransmitTx {
         data
     }
 
-    fn ixs(&self) -> ProgramResult<Vec<OwnedAtomicIxBatch>> {
+    async fn ixs(&self) -> ProgramResult<Vec<OwnedAtomicIxBatch>> {
         let data = self.tx_data(0, self.rlp.to_vec());
-        let emulation = self.tx_builder.emulate(&data, &self.resource.payer_key())?;
+        let emulation = self
+            .tx_builder
+            .emulate(&data, &self.resource.payer_key())
+            .await?;
 
+        let usage = compute_usage(&emulation);
         let mut offset = 0;
 
         let ixs = into_chunks(self.rlp.to_vec(), TRANSMIT_TX_SIZE)
@@ -68,7 +73,7 @@ impl TransmitTx {
                 data
             })
             .map(|data| self.tx_builder.build_ix(&emulation, data))
-            .map(|ix| OwnedAtomicIxBatch::new_owned(vec![ix]))
+            .map(|ix| OwnedAtomicIxBatch::new_owned(vec![ix]).with_usage(usage))
             .collect();
 
         Ok(ixs)
@@ -78,35 +83,43 @@ impl TransmitTx {
 #[async_trait]
 impl AdvanceTx<'_> for TransmitTx {
     type Error = RomeEvmError;
-    fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
+    async fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
         match &mut self.step {
             Steps::Init => {
-                let ixs = self.ixs()?;
+                let ixs = self.ixs().await?;
                 let limit = MAX_PERMITTED_DATA_INCREASE / TRANSMIT_TX_SIZE;
                 let mut batches = into_chunks(ixs, limit);
                 batches.reverse();
 
                 self.step = Steps::Execute(batches);
-                self.advance()
+                self.advance().await
             }
             Steps::Execute(batches) => {
                 if let Some(batch) = batches.pop() {
                     Ok(IxExecStepBatch::Parallel(batch, TxVersion::Legacy))
                 } else {
                     self.step = Steps::Complete;
-                    self.advance()
+                    self.advance().await
                 }
             }
             _ => Ok(IxExecStepBatch::End),
         }
     }
-    fn advance_with_version(
+    async fn advance_with_version(
         &mut self,
         _: TxVersion,
     ) -> Result<IxExecStepBatch<'static>, Self::Error> {
         unreachable!()
     }
-    fn payer(&self) -> Arc<Keypair> {
+    fn payer(&self) -> SharedSigner {
         self.resource.payer()
     }
+    fn remaining_steps(&self) -> Option<usize> {
+        match &self.step {
+            // the instructions are not built yet
+            Steps::Init => None,
+            Steps::Execute(batches) => Some(batches.len()),
+            Steps::Complete => Some(0),
+        }
+    }
 }
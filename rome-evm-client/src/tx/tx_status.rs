@@ -0,0 +1,292 @@
+use crate::error::{ProgramResult, RomeEvmError};
+use crate::tx::Iterable;
+use async_trait::async_trait;
+use ethers::types::TxHash;
+use rome_solana::batch::{AdvanceTx, IxExecStepBatch, TxVersion};
+use rome_solana::signer::SharedSigner;
+use serde::{Serialize, Serializer};
+use solana_rpc_client_api::client_error::{Error as ClientError, ErrorKind};
+use solana_rpc_client_api::request::RpcError;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Time a status is kept after its last update
+pub const DEFAULT_TX_STATUS_TTL: Duration = Duration::from_secs(600);
+
+/// Status of a transaction submitted by the client, before and after it is indexed
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum TxStatus {
+    /// Waiting for a free resource (payer and holder) and being emulated
+    Queued,
+
+    /// Sending the Solana transactions of the execution step, starting from 1, out of `steps`
+    /// if the total number of steps is known
+    Sending {
+        step: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        steps: Option<usize>,
+    },
+
+    /// All Solana transactions of the execution are confirmed
+    Confirmed {
+        #[serde(serialize_with = "serialize_signatures")]
+        signatures: Vec<Signature>,
+    },
+
+    /// Execution failed, the transaction will not be indexed
+    Failed { error: String },
+
+    /// Sending or confirmation of the Solana transactions timed out, the transaction may still
+    /// be executed and indexed
+    Unconfirmed { error: String },
+
+    /// Receipt of the transaction is stored by the indexer
+    Indexed,
+}
+
+impl TxStatus {
+    /// Status of the transaction whose execution returned `err`
+    pub fn from_error(err: &anyhow::Error) -> Self {
+        let error = err.to_string();
+        if is_unconfirmed(err) {
+            Self::Unconfirmed { error }
+        } else {
+            Self::Failed { error }
+        }
+    }
+}
+
+// true if the error leaves the outcome of the sent Solana transactions unknown
+fn is_unconfirmed(err: &anyhow::Error) -> bool {
+    err.chain()
+        .filter_map(|err| err.downcast_ref::<ClientError>())
+        .any(|err| match err.kind() {
+            ErrorKind::Io(_) => true,
+            ErrorKind::Reqwest(err) => err.is_timeout(),
+            ErrorKind::RpcError(RpcError::ForUser(msg)) => {
+                msg.starts_with("unable to confirm transaction")
+                    || msg.starts_with("transaction not finalized")
+            }
+            _ => false,
+        })
+}
+
+fn serialize_signatures<S: Serializer>(
+    signatures: &[Signature],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(signatures.iter().map(Signature::to_string))
+}
+
+/// Statuses of the submitted transactions by hash, expiring after the TTL
+#[derive(Clone)]
+pub struct TxStatuses {
+    inner: Arc<Mutex<Inner>>,
+    ttl: Duration,
+}
+
+#[derive(Default)]
+struct Inner {
+    statuses: HashMap<TxHash, (TxStatus, Instant)>,
+    // updates in order of time, the oldest ones are checked for expiration
+    updates: VecDeque<(Instant, TxHash)>,
+}
+
+impl Default for TxStatuses {
+    fn default() -> Self {
+        Self::new(DEFAULT_TX_STATUS_TTL)
+    }
+}
+
+impl TxStatuses {
+    /// Creates a new [TxStatuses] keeping every status for `ttl` after its last update
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner::default())),
+            ttl,
+        }
+    }
+
+    /// Sets the status of the transaction, removing the expired ones
+    pub fn set(&self, hash: TxHash, status: TxStatus) {
+        let now = Instant::now();
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+
+        while let Some((updated, expired)) = inner.updates.front().copied() {
+            if now.duration_since(updated) < self.ttl {
+                break;
+            }
+
+            inner.updates.pop_front();
+            // the status is kept if it is updated later
+            if inner.statuses.get(&expired).map(|(_, last)| *last) == Some(updated) {
+                inner.statuses.remove(&expired);
+            }
+        }
+
+        inner.statuses.insert(hash, (status, now));
+        inner.updates.push_back((now, hash));
+    }
+
+    /// Returns the status of the transaction if it is not expired
+    pub fn get(&self, hash: &TxHash) -> Option<TxStatus> {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+
+        inner
+            .statuses
+            .get(hash)
+            .filter(|(_, updated)| updated.elapsed() < self.ttl)
+            .map(|(status, _)| status.clone())
+    }
+}
+
+/// [Iterable] reporting its execution steps to [TxStatuses]
+pub struct TrackedTx {
+    tx: Iterable,
+    hash: TxHash,
+    statuses: TxStatuses,
+    step: usize,
+}
+
+impl TrackedTx {
+    pub fn new(tx: Iterable, hash: TxHash, statuses: TxStatuses) -> Self {
+        Self {
+            tx,
+            hash,
+            statuses,
+            step: 0,
+        }
+    }
+
+    fn track(
+        &mut self,
+        batch: ProgramResult<IxExecStepBatch<'static>>,
+    ) -> ProgramResult<IxExecStepBatch<'static>> {
+        if let Ok(batch) = &batch {
+            if !matches!(batch, IxExecStepBatch::End) {
+                self.step += 1;
+                let status = TxStatus::Sending {
+                    step: self.step,
+                    steps: self.tx.remaining_steps().map(|left| self.step + left),
+                };
+                self.statuses.set(self.hash, status);
+            }
+        }
+
+        batch
+    }
+}
+
+#[async_trait]
+impl AdvanceTx<'static> for TrackedTx {
+    type Error = RomeEvmError;
+
+    async fn advance(&mut self) -> ProgramResult<IxExecStepBatch<'static>> {
+        let batch = self.tx.advance().await;
+        self.track(batch)
+    }
+
+    async fn advance_with_version(
+        &mut self,
+        version: TxVersion,
+    ) -> ProgramResult<IxExecStepBatch<'static>> {
+        let batch = self.tx.advance_with_version(version).await;
+        self.track(batch)
+    }
+
+    fn payer(&self) -> SharedSigner {
+        self.tx.payer()
+    }
+
+    fn created_alt(&self) -> Option<Pubkey> {
+        self.tx.created_alt()
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.tx.remaining_steps()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn statuses_expire_after_ttl() {
+        let statuses = TxStatuses::new(Duration::from_millis(50));
+        let (first, second) = (TxHash::random(), TxHash::random());
+
+        statuses.set(first, TxStatus::Queued);
+        let sending = TxStatus::Sending {
+            step: 1,
+            steps: Some(2),
+        };
+        statuses.set(first, sending.clone());
+        assert_eq!(statuses.get(&first), Some(sending));
+        assert_eq!(statuses.get(&second), None);
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(statuses.get(&first), None);
+
+        // expired statuses are removed on update
+        statuses.set(second, TxStatus::Indexed);
+        let inner = statuses.inner.lock().unwrap();
+        assert_eq!(inner.statuses.len(), 1);
+        assert_eq!(inner.updates.len(), 1);
+    }
+
+    #[test]
+    fn serialize_status() {
+        let status = TxStatus::Confirmed {
+            signatures: vec![Signature::default()],
+        };
+        assert_eq!(
+            serde_json::to_value(status).unwrap(),
+            serde_json::json!({
+                "status": "confirmed",
+                "signatures": [Signature::default().to_string()],
+            })
+        );
+        let sending = TxStatus::Sending {
+            step: 2,
+            steps: None,
+        };
+        assert_eq!(
+            serde_json::to_value(sending).unwrap(),
+            serde_json::json!({"status": "sending", "step": 2})
+        );
+        let sending = TxStatus::Sending {
+            step: 2,
+            steps: Some(3),
+        };
+        assert_eq!(
+            serde_json::to_value(sending).unwrap(),
+            serde_json::json!({"status": "sending", "step": 2, "steps": 3})
+        );
+    }
+
+    #[test]
+    fn status_from_error() {
+        let timeout: ClientError =
+            RpcError::ForUser("unable to confirm transaction. This can happen...".to_string())
+                .into();
+        assert!(matches!(
+            TxStatus::from_error(&timeout.into()),
+            TxStatus::Unconfirmed { .. }
+        ));
+
+        let failed: ClientError = RpcError::ForUser("AccountNotFound".to_string()).into();
+        assert!(matches!(
+            TxStatus::from_error(&failed.into()),
+            TxStatus::Failed { .. }
+        ));
+        assert!(matches!(
+            TxStatus::from_error(&anyhow::anyhow!("Failed to advance tx")),
+            TxStatus::Failed { .. }
+        ));
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
 use emulator::Emulation;
 use ethers::types::{NameOrAddress, TransactionRequest, U256};
 use rome_evm::{tx::legacy::Legacy as LegacyTx, ExitReason, H160 as EvmH160};
+use rome_solana::compute_units::ComputeUsage;
 
 pub struct RomeEvmUtil;
 
@@ -83,3 +84,33 @@ pub fn check_accounts_len(emulation: &Emulation) -> ProgramResult<()> {
 
     Ok(())
 }
+
+// compute resources of the Solana transaction executing the emulated instruction,
+// the emulator reports the usage of a single iteration of an iterative instruction
+pub fn compute_usage(emulation: &Emulation) -> ComputeUsage {
+    ComputeUsage {
+        units: emulation.compute_units as u64,
+        heap: emulation.heap_usage as u64,
+    }
+}
+
+// check the transaction fits into a single Solana transaction.
+// index - position of the transaction in a bundle, used to report the error
+pub fn check_atomic(emulation: &Emulation, index: usize) -> ProgramResult<()> {
+    if emulation.is_atomic {
+        return Ok(());
+    }
+
+    let iterations = emulation
+        .vm
+        .as_ref()
+        .map(|vm| vm.iteration_count as u64)
+        .unwrap_or(1);
+
+    Err(RomeEvmError::NonAtomicTx(
+        index,
+        iterations,
+        compute_usage(emulation).units,
+        emulation.accounts.len(),
+    ))
+}
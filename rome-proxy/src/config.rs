@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use ethers::types::U256;
+use rome_evm_client::error::ProgramResult;
+use rome_evm_client::indexer::config::{
+    RollupIndexerConfig, SolanaBlockLoaderConfig, StorageConfig,
+};
+use rome_evm_client::resources::{Payer, PayerConfig};
+use rome_evm_client::{
+    EmulatorPool, RomeEVMClient, DEFAULT_EMULATION_TIMEOUT, DEFAULT_MAX_EMULATIONS,
+    DEFAULT_MAX_LOGS, DEFAULT_MAX_LOGS_BLOCK_RANGE,
+};
+use rome_solana::compute_units::{ComputeUnitLimit, HeapFrame};
+use rome_solana::config::SolanaConfig;
+use rome_solana::priority_fee::PriorityFee;
+use rome_solana::tower::SolanaTower;
+use solana_sdk::clock::Slot;
+use solana_sdk::pubkey::Pubkey;
+use tokio::task::JoinHandle;
+
+/// Configuration of the proxy
+#[derive(Debug, serde::Deserialize)]
+pub struct ProxyConfig {
+    /// Config to solana rpc
+    #[serde(flatten)]
+    pub solana_config: SolanaConfig,
+
+    /// Priority fee policy of the Solana transactions
+    #[serde(default)]
+    pub priority_fee: PriorityFee,
+
+    /// Compute unit limit policy of the Solana transactions
+    #[serde(default)]
+    pub compute_unit_limit: ComputeUnitLimit,
+
+    /// Heap frame policy of the Solana transactions
+    #[serde(default)]
+    pub heap_frame: HeapFrame,
+
+    /// Maps chain id to the config of the rollup served
+    pub chains: HashMap<u64, ChainConfig>,
+}
+
+impl ProxyConfig {
+    /// Load the configuration from a yaml (.yml, .yaml) or json file
+    pub async fn load(path: PathBuf) -> anyhow::Result<Self> {
+        let yml = matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("yml" | "yaml")
+        );
+
+        let file = tokio::fs::read_to_string(path)
+            .await
+            .context("Failed to read config file")?;
+
+        let config = if yml {
+            serde_yaml::from_str(&file).context("Failed to parse config file")?
+        } else {
+            serde_json::from_str(&file).context("Failed to parse config file")?
+        };
+
+        Ok(config)
+    }
+}
+
+/// Configuration of a rollup served by the proxy
+#[derive(Debug, serde::Deserialize)]
+pub struct ChainConfig {
+    /// Address of the HTTP and WebSocket server
+    pub listen: SocketAddr,
+
+    /// Rollup program address
+    pub program_id: String,
+
+    /// Payer pool of the rollup
+    pub payers: Vec<PayerConfig>,
+
+    /// Gas price, in wei
+    #[serde(default)]
+    pub gas_price: u64,
+
+    /// Storage of the Solana and Ethereum blocks
+    pub storage: StorageConfig,
+
+    /// Rollup indexer
+    pub indexer: RollupIndexerConfig,
+
+    /// Loader of the Solana blocks, omitted if the blocks are loaded by the relayer
+    #[serde(default)]
+    pub block_loader: Option<SolanaBlockLoaderConfig>,
+
+    /// Slot to start indexing from
+    #[serde(default)]
+    pub start_slot: Option<Slot>,
+
+    /// Time the statuses of the submitted transactions are kept, in seconds
+    #[serde(default)]
+    pub tx_status_ttl: Option<u64>,
+
+    /// Maximum number of emulations running at the same time
+    #[serde(default)]
+    pub max_emulations: Option<usize>,
+
+    /// Maximum time of an emulation, including the wait for a free slot, in seconds
+    #[serde(default)]
+    pub emulation_timeout: Option<u64>,
+
+    /// Maximum number of blocks searched by `eth_getLogs`
+    #[serde(default)]
+    pub max_logs_block_range: Option<u64>,
+
+    /// Maximum number of logs returned by `eth_getLogs`
+    #[serde(default)]
+    pub max_logs: Option<usize>,
+}
+
+impl ChainConfig {
+    /// Create the client of the rollup and start its indexer
+    pub async fn start(
+        self,
+        chain_id: u64,
+        solana: SolanaTower,
+    ) -> anyhow::Result<(Arc<RomeEVMClient>, JoinHandle<ProgramResult<()>>)> {
+        let program_id = Pubkey::try_from(self.program_id.as_str())
+            .map_err(|e| anyhow::anyhow!("Failed to parse program id: {:?}", e))?;
+
+        if self.indexer.block_parser.chain_id != chain_id {
+            return Err(anyhow::anyhow!(
+                "Chain id {} of the block parser does not match the chain id {}",
+                self.indexer.block_parser.chain_id,
+                chain_id
+            ));
+        }
+
+        let commitment = solana.client().commitment().commitment;
+        let payers = Payer::from_config_list(&self.payers).await?;
+        let (solana_block_storage, ethereum_block_storage) = self.storage.init().await?;
+
+        let mut client = RomeEVMClient::new(
+            chain_id,
+            program_id,
+            solana,
+            commitment,
+            ethereum_block_storage,
+            payers,
+            U256::from(self.gas_price),
+        );
+        if let Some(ttl) = self.tx_status_ttl {
+            client = client.with_tx_status_ttl(Duration::from_secs(ttl));
+        }
+        if self.max_emulations.is_some() || self.emulation_timeout.is_some() {
+            client = client.with_emulator_pool(EmulatorPool::new(
+                self.max_emulations.unwrap_or(DEFAULT_MAX_EMULATIONS),
+                self.emulation_timeout
+                    .map(Duration::from_secs)
+                    .unwrap_or(DEFAULT_EMULATION_TIMEOUT),
+            ));
+        }
+        let max_logs_block_range = self
+            .max_logs_block_range
+            .unwrap_or(DEFAULT_MAX_LOGS_BLOCK_RANGE);
+        let max_logs = self.max_logs.unwrap_or(DEFAULT_MAX_LOGS);
+        let client = Arc::new(client.with_log_limits(max_logs_block_range, max_logs));
+
+        let block_loader = self
+            .block_loader
+            .map(|config| config.init(solana_block_storage.clone()));
+
+        let indexer = client.start_indexing_with_config(
+            &self.indexer,
+            block_loader,
+            solana_block_storage,
+            self.start_slot,
+        )?;
+
+        Ok((client, indexer))
+    }
+}
@@ -0,0 +1,72 @@
+mod config;
+mod rpc;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use clap::Parser;
+use config::ProxyConfig;
+use rome_solana::indexers::clock::SolanaClockIndexer;
+use rome_solana::tower::SolanaTower;
+use rome_solana::types::AsyncAtomicRpcClient;
+
+/// Ethereum JSON-RPC server of Rome rollups.
+///
+/// Serves the `eth_*`, `net_*`, `web3_*` and `rome_*` methods of every configured rollup
+/// over HTTP and WebSocket, and indexes its blocks.
+#[derive(Parser, Debug)]
+#[command(name = "rome-proxy", version)]
+struct Cli {
+    /// Path to the proxy config, JSON or YAML (.yml, .yaml)
+    #[arg(long, short)]
+    config: PathBuf,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let cli = Cli::parse();
+    let config = ProxyConfig::load(cli.config).await?;
+
+    let rpc_client: AsyncAtomicRpcClient = Arc::new(config.solana_config.into());
+    let clock_indexer = SolanaClockIndexer::new(rpc_client.clone())
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create clock indexer: {:?}", e))?;
+    let clock = clock_indexer.get_current_clock();
+    tokio::spawn(clock_indexer.start());
+
+    let solana = SolanaTower::new(rpc_client, clock)
+        .with_priority_fee(config.priority_fee)
+        .with_compute_unit_limit(config.compute_unit_limit)
+        .with_heap_frame(config.heap_frame);
+
+    let mut servers = vec![];
+    let mut indexers = vec![];
+    for (chain_id, chain) in config.chains {
+        let listen = chain.listen;
+        let (client, indexer) = chain.start(chain_id, solana.clone()).await?;
+
+        servers.push(rpc::serve(listen, client).await?);
+        indexers.push(indexer);
+    }
+
+    if indexers.is_empty() {
+        return Err(anyhow::anyhow!("No chains configured"));
+    }
+
+    // the proxy stops when any of the indexers stops
+    tokio::select! {
+        (result, _, _) = futures::future::select_all(indexers) => {
+            result??;
+            Err(anyhow::anyhow!("Indexer stopped"))
+        }
+        _ = tokio::signal::ctrl_c() => {
+            for server in servers {
+                let _ = server.stop();
+            }
+
+            Ok(())
+        }
+    }
+}
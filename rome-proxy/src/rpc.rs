@@ -0,0 +1,562 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use ethers::types::{
+    Address, BlockId, BlockNumber, Bytes, FeeHistory, Filter, Log, Transaction, TransactionReceipt,
+    TransactionRequest, TxHash, H256, U256, U64,
+};
+use ethers::utils::keccak256;
+use futures::{Stream, StreamExt};
+use jsonrpsee::core::{async_trait, RpcResult, SubscriptionResult};
+use jsonrpsee::proc_macros::rpc;
+use jsonrpsee::server::{Server, ServerHandle};
+use jsonrpsee::types::error::INVALID_PARAMS_CODE;
+use jsonrpsee::types::{ErrorObject, ErrorObjectOwned};
+use jsonrpsee::{PendingSubscriptionSink, RpcModule, SubscriptionMessage};
+use rome_evm_client::error::RomeEvmError;
+use rome_evm_client::indexer::BlockType;
+use rome_evm_client::tx::TxStatus;
+use rome_evm_client::RomeEVMClient;
+use serde::Serialize;
+
+/// Error code of the reverted execution
+const EXECUTION_REVERTED_CODE: i32 = 3;
+
+/// Error code of the failures of the server
+const SERVER_ERROR_CODE: i32 = -32000;
+
+/// Methods of the `eth` namespace
+#[rpc(server, namespace = "eth")]
+pub trait EthApi {
+    #[method(name = "chainId")]
+    fn chain_id(&self) -> RpcResult<U64>;
+
+    #[method(name = "blockNumber")]
+    async fn block_number(&self) -> RpcResult<U64>;
+
+    #[method(name = "gasPrice")]
+    fn gas_price(&self) -> RpcResult<U256>;
+
+    #[method(name = "maxPriorityFeePerGas")]
+    fn max_priority_fee_per_gas(&self) -> RpcResult<U256>;
+
+    #[method(name = "feeHistory")]
+    async fn fee_history(
+        &self,
+        count: U64,
+        newest_block: BlockNumber,
+        reward_percentiles: Option<Vec<f64>>,
+    ) -> RpcResult<FeeHistory>;
+
+    #[method(name = "getBalance")]
+    async fn get_balance(&self, address: Address, block: Option<BlockId>) -> RpcResult<U256>;
+
+    #[method(name = "getTransactionCount")]
+    async fn get_transaction_count(
+        &self,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> RpcResult<U64>;
+
+    #[method(name = "getCode")]
+    async fn get_code(&self, address: Address, block: Option<BlockId>) -> RpcResult<Bytes>;
+
+    #[method(name = "getStorageAt")]
+    async fn get_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        block: Option<BlockId>,
+    ) -> RpcResult<H256>;
+
+    #[method(name = "call")]
+    async fn call(&self, tx: TransactionRequest, block: Option<BlockId>) -> RpcResult<Bytes>;
+
+    #[method(name = "estimateGas")]
+    async fn estimate_gas(&self, tx: TransactionRequest, block: Option<BlockId>)
+        -> RpcResult<U256>;
+
+    #[method(name = "sendRawTransaction")]
+    async fn send_raw_transaction(&self, rlp: Bytes) -> RpcResult<TxHash>;
+
+    #[method(name = "getTransactionByHash")]
+    async fn get_transaction_by_hash(&self, hash: TxHash) -> RpcResult<Option<Transaction>>;
+
+    #[method(name = "getTransactionReceipt")]
+    async fn get_transaction_receipt(&self, hash: TxHash) -> RpcResult<Option<TransactionReceipt>>;
+
+    #[method(name = "getBlockByNumber")]
+    async fn get_block_by_number(
+        &self,
+        number: BlockNumber,
+        full_transactions: bool,
+    ) -> RpcResult<Option<BlockType>>;
+
+    #[method(name = "getBlockByHash")]
+    async fn get_block_by_hash(
+        &self,
+        hash: H256,
+        full_transactions: bool,
+    ) -> RpcResult<Option<BlockType>>;
+
+    #[method(name = "getBlockReceipts")]
+    async fn get_block_receipts(
+        &self,
+        block: BlockId,
+    ) -> RpcResult<Option<Vec<TransactionReceipt>>>;
+
+    #[method(name = "getTransactionByBlockNumberAndIndex")]
+    async fn get_transaction_by_block_number_and_index(
+        &self,
+        number: BlockNumber,
+        index: U64,
+    ) -> RpcResult<Option<Transaction>>;
+
+    #[method(name = "getTransactionByBlockHashAndIndex")]
+    async fn get_transaction_by_block_hash_and_index(
+        &self,
+        hash: H256,
+        index: U64,
+    ) -> RpcResult<Option<Transaction>>;
+
+    #[method(name = "getBlockTransactionCountByNumber")]
+    async fn get_block_transaction_count_by_number(
+        &self,
+        number: BlockNumber,
+    ) -> RpcResult<Option<U64>>;
+
+    #[method(name = "getBlockTransactionCountByHash")]
+    async fn get_block_transaction_count_by_hash(&self, hash: H256) -> RpcResult<Option<U64>>;
+
+    #[method(name = "getUncleCountByBlockNumber")]
+    async fn get_uncle_count_by_block_number(&self, number: BlockNumber) -> RpcResult<Option<U64>>;
+
+    #[method(name = "getUncleCountByBlockHash")]
+    async fn get_uncle_count_by_block_hash(&self, hash: H256) -> RpcResult<Option<U64>>;
+
+    #[method(name = "getLogs")]
+    async fn get_logs(&self, filter: Filter) -> RpcResult<Vec<Log>>;
+
+    #[method(name = "syncing")]
+    fn syncing(&self) -> RpcResult<bool>;
+
+    #[method(name = "accounts")]
+    fn accounts(&self) -> RpcResult<Vec<Address>>;
+}
+
+/// Subscriptions of the `eth` namespace, served over WebSocket
+#[rpc(server, namespace = "eth")]
+pub trait EthPubSubApi {
+    /// Subscribes to `newHeads` or `logs` matching an optional filter
+    #[subscription(name = "subscribe" => "subscription", unsubscribe = "unsubscribe", item = serde_json::Value)]
+    async fn subscribe(&self, kind: String, filter: Option<Filter>) -> SubscriptionResult;
+}
+
+/// Methods of the `rome` namespace
+#[rpc(server, namespace = "rome")]
+pub trait RomeApi {
+    /// Status of a transaction from its submission to indexing
+    #[method(name = "transactionStatus")]
+    async fn transaction_status(&self, hash: TxHash) -> RpcResult<Option<TxStatus>>;
+}
+
+/// Methods of the `net` namespace
+#[rpc(server, namespace = "net")]
+pub trait NetApi {
+    #[method(name = "version")]
+    fn version(&self) -> RpcResult<String>;
+
+    #[method(name = "listening")]
+    fn listening(&self) -> RpcResult<bool>;
+
+    #[method(name = "peerCount")]
+    fn peer_count(&self) -> RpcResult<U64>;
+}
+
+/// Methods of the `web3` namespace
+#[rpc(server, namespace = "web3")]
+pub trait Web3Api {
+    #[method(name = "clientVersion")]
+    fn client_version(&self) -> RpcResult<String>;
+
+    #[method(name = "sha3")]
+    fn sha3(&self, data: Bytes) -> RpcResult<H256>;
+}
+
+/// Ethereum JSON-RPC API of a rollup served by [RomeEVMClient]
+#[derive(Clone)]
+pub struct RomeRpc {
+    client: Arc<RomeEVMClient>,
+}
+
+impl RomeRpc {
+    /// Creates a new [RomeRpc]
+    pub fn new(client: Arc<RomeEVMClient>) -> Self {
+        Self { client }
+    }
+
+    /// Methods of all the namespaces
+    pub fn into_module(self) -> anyhow::Result<RpcModule<()>> {
+        let mut module = RpcModule::new(());
+        module.merge(EthApiServer::into_rpc(self.clone()))?;
+        module.merge(EthPubSubApiServer::into_rpc(self.clone()))?;
+        module.merge(NetApiServer::into_rpc(self.clone()))?;
+        module.merge(RomeApiServer::into_rpc(self.clone()))?;
+        module.merge(Web3ApiServer::into_rpc(self))?;
+
+        Ok(module)
+    }
+}
+
+/// Serve the API of the rollup over HTTP and WebSocket
+pub async fn serve(listen: SocketAddr, client: Arc<RomeEVMClient>) -> anyhow::Result<ServerHandle> {
+    let server = Server::builder().build(listen).await?;
+    tracing::info!(
+        "Serving chain {} on {}",
+        client.chain_id(),
+        server.local_addr()?
+    );
+
+    Ok(server.start(RomeRpc::new(client).into_module()?))
+}
+
+// the state is emulated on the latest block only
+fn check_block(block: Option<BlockId>) -> RpcResult<()> {
+    match block {
+        None | Some(BlockId::Number(BlockNumber::Latest | BlockNumber::Pending)) => Ok(()),
+        Some(block) => Err(ErrorObject::owned(
+            INVALID_PARAMS_CODE,
+            format!(
+                "state of block {:?} is not available, only the latest state is",
+                block
+            ),
+            None::<()>,
+        )),
+    }
+}
+
+// forwards the items of the stream until the subscription is closed. The subscription is
+// closed with an error if the stream ends, after the subscriber lagged behind the indexer
+async fn pipe<T: Serialize>(
+    pending: PendingSubscriptionSink,
+    stream: impl Stream<Item = T>,
+) -> SubscriptionResult {
+    let sink = pending.accept().await?;
+    futures::pin_mut!(stream);
+
+    loop {
+        tokio::select! {
+            _ = sink.closed() => break Ok(()),
+            item = stream.next() => {
+                let Some(item) = item else {
+                    break Err("subscription lagged behind or the indexer stopped, events may be \
+                        missed: resubscribe and fetch them with eth_getLogs"
+                        .into());
+                };
+                if sink.send(SubscriptionMessage::from_json(&item)?).await.is_err() {
+                    break Ok(());
+                }
+            }
+        }
+    }
+}
+
+fn rpc_error(err: RomeEvmError) -> ErrorObjectOwned {
+    match err {
+        RomeEvmError::EmulationRevert(message, data) => {
+            ErrorObject::owned(EXECUTION_REVERTED_CODE, message, Some(data))
+        }
+        err => ErrorObject::owned(SERVER_ERROR_CODE, err.to_string(), None::<()>),
+    }
+}
+
+#[async_trait]
+impl EthApiServer for RomeRpc {
+    fn chain_id(&self) -> RpcResult<U64> {
+        Ok(self.client.chain_id().into())
+    }
+
+    async fn block_number(&self) -> RpcResult<U64> {
+        self.client.block_number().await.map_err(rpc_error)
+    }
+
+    fn gas_price(&self) -> RpcResult<U256> {
+        self.client.gas_price().map_err(rpc_error)
+    }
+
+    fn max_priority_fee_per_gas(&self) -> RpcResult<U256> {
+        Ok(U256::zero())
+    }
+
+    async fn fee_history(
+        &self,
+        count: U64,
+        newest_block: BlockNumber,
+        reward_percentiles: Option<Vec<f64>>,
+    ) -> RpcResult<FeeHistory> {
+        self.client
+            .fee_history(
+                count.as_u64(),
+                BlockId::Number(newest_block),
+                reward_percentiles.unwrap_or_default(),
+            )
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn get_balance(&self, address: Address, block: Option<BlockId>) -> RpcResult<U256> {
+        check_block(block)?;
+        self.client.get_balance(address).await.map_err(rpc_error)
+    }
+
+    async fn get_transaction_count(
+        &self,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> RpcResult<U64> {
+        check_block(block)?;
+        self.client
+            .transaction_count(address)
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn get_code(&self, address: Address, block: Option<BlockId>) -> RpcResult<Bytes> {
+        check_block(block)?;
+        self.client.get_code(address).await.map_err(rpc_error)
+    }
+
+    async fn get_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        block: Option<BlockId>,
+    ) -> RpcResult<H256> {
+        check_block(block)?;
+        let value = self
+            .client
+            .eth_get_storage_at(address, slot)
+            .await
+            .map_err(rpc_error)?;
+
+        let mut buf = [0u8; 32];
+        value.to_big_endian(&mut buf);
+
+        Ok(H256::from(buf))
+    }
+
+    async fn call(&self, tx: TransactionRequest, block: Option<BlockId>) -> RpcResult<Bytes> {
+        check_block(block)?;
+        self.client.call(&tx).await.map_err(rpc_error)
+    }
+
+    async fn estimate_gas(
+        &self,
+        tx: TransactionRequest,
+        block: Option<BlockId>,
+    ) -> RpcResult<U256> {
+        check_block(block)?;
+        self.client.estimate_gas(&tx).await.map_err(rpc_error)
+    }
+
+    async fn send_raw_transaction(&self, rlp: Bytes) -> RpcResult<TxHash> {
+        self.client.send_transaction(rlp).await.map_err(rpc_error)
+    }
+
+    async fn get_transaction_by_hash(&self, hash: TxHash) -> RpcResult<Option<Transaction>> {
+        self.client.get_transaction(&hash).await.map_err(rpc_error)
+    }
+
+    async fn get_transaction_receipt(&self, hash: TxHash) -> RpcResult<Option<TransactionReceipt>> {
+        self.client
+            .get_transaction_receipt(&hash)
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn get_block_by_number(
+        &self,
+        number: BlockNumber,
+        full_transactions: bool,
+    ) -> RpcResult<Option<BlockType>> {
+        self.client
+            .get_block(BlockId::Number(number), full_transactions)
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn get_block_by_hash(
+        &self,
+        hash: H256,
+        full_transactions: bool,
+    ) -> RpcResult<Option<BlockType>> {
+        self.client
+            .get_block(BlockId::Hash(hash), full_transactions)
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn get_block_receipts(
+        &self,
+        block: BlockId,
+    ) -> RpcResult<Option<Vec<TransactionReceipt>>> {
+        self.client
+            .get_block_receipts(block)
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn get_transaction_by_block_number_and_index(
+        &self,
+        number: BlockNumber,
+        index: U64,
+    ) -> RpcResult<Option<Transaction>> {
+        self.client
+            .get_transaction_by_block_and_index(BlockId::Number(number), index)
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn get_transaction_by_block_hash_and_index(
+        &self,
+        hash: H256,
+        index: U64,
+    ) -> RpcResult<Option<Transaction>> {
+        self.client
+            .get_transaction_by_block_and_index(BlockId::Hash(hash), index)
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn get_block_transaction_count_by_number(
+        &self,
+        number: BlockNumber,
+    ) -> RpcResult<Option<U64>> {
+        self.client
+            .get_block_transaction_count(BlockId::Number(number))
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn get_block_transaction_count_by_hash(&self, hash: H256) -> RpcResult<Option<U64>> {
+        self.client
+            .get_block_transaction_count(BlockId::Hash(hash))
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn get_uncle_count_by_block_number(&self, number: BlockNumber) -> RpcResult<Option<U64>> {
+        self.client
+            .get_uncle_count(BlockId::Number(number))
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn get_uncle_count_by_block_hash(&self, hash: H256) -> RpcResult<Option<U64>> {
+        self.client
+            .get_uncle_count(BlockId::Hash(hash))
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn get_logs(&self, filter: Filter) -> RpcResult<Vec<Log>> {
+        self.client.get_logs(&filter).await.map_err(rpc_error)
+    }
+
+    fn syncing(&self) -> RpcResult<bool> {
+        Ok(false)
+    }
+
+    fn accounts(&self) -> RpcResult<Vec<Address>> {
+        Ok(vec![])
+    }
+}
+
+#[async_trait]
+impl EthPubSubApiServer for RomeRpc {
+    async fn subscribe(
+        &self,
+        pending: PendingSubscriptionSink,
+        kind: String,
+        filter: Option<Filter>,
+    ) -> SubscriptionResult {
+        let events = self.client.events();
+        match kind.as_str() {
+            "newHeads" => pipe(pending, events.new_heads()).await,
+            "logs" => pipe(pending, events.logs(&filter.unwrap_or_default())).await,
+            kind => {
+                pending
+                    .reject(ErrorObject::owned(
+                        INVALID_PARAMS_CODE,
+                        format!("unsupported subscription {}", kind),
+                        None::<()>,
+                    ))
+                    .await;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl RomeApiServer for RomeRpc {
+    async fn transaction_status(&self, hash: TxHash) -> RpcResult<Option<TxStatus>> {
+        self.client
+            .get_transaction_status(&hash)
+            .await
+            .map_err(rpc_error)
+    }
+}
+
+impl NetApiServer for RomeRpc {
+    fn version(&self) -> RpcResult<String> {
+        Ok(self.client.chain_id().to_string())
+    }
+
+    fn listening(&self) -> RpcResult<bool> {
+        Ok(true)
+    }
+
+    fn peer_count(&self) -> RpcResult<U64> {
+        Ok(U64::zero())
+    }
+}
+
+impl Web3ApiServer for RomeRpc {
+    fn client_version(&self) -> RpcResult<String> {
+        Ok(format!("rome-proxy/v{}", env!("CARGO_PKG_VERSION")))
+    }
+
+    fn sha3(&self, data: Bytes) -> RpcResult<H256> {
+        Ok(keccak256(&data).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latest_state_only() {
+        assert!(check_block(None).is_ok());
+        assert!(check_block(Some(BlockId::Number(BlockNumber::Latest))).is_ok());
+        assert!(check_block(Some(BlockId::Number(BlockNumber::Pending))).is_ok());
+
+        let err = check_block(Some(BlockId::Number(BlockNumber::Number(1.into())))).unwrap_err();
+        assert_eq!(err.code(), INVALID_PARAMS_CODE);
+    }
+
+    #[test]
+    fn revert_keeps_data() {
+        let err = rpc_error(RomeEvmError::EmulationRevert(
+            "execution reverted: nope".to_string(),
+            "0x08c379a0".to_string(),
+        ));
+        assert_eq!(err.code(), EXECUTION_REVERTED_CODE);
+        assert_eq!(err.message(), "execution reverted: nope");
+        assert_eq!(err.data().unwrap().get(), "\"0x08c379a0\"");
+
+        let err = rpc_error(RomeEvmError::NoFreeHolders);
+        assert_eq!(err.code(), SERVER_ERROR_CODE);
+    }
+}
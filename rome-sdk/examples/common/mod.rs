@@ -1,6 +1,4 @@
-use anyhow::Context;
-use ethers::signers::{LocalWallet, Signer};
-use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::signers::LocalWallet;
 use ethers::types::{Address, TransactionRequest};
 use rome_sdk::{EthSignedTxTuple, Rome};
 use solana_sdk::instruction::Instruction;
@@ -33,55 +31,28 @@ pub fn create_solana_payer() -> Keypair {
     Keypair::from_bytes(SOLANA_KEYPAIR).unwrap()
 }
 
-/// Sign a transaction with the wallet and return the [EthSignedTxTuple]
-pub async fn sign_tx(
-    wallet: &LocalWallet,
-    tx: TypedTransaction,
-) -> anyhow::Result<EthSignedTxTuple> {
-    let sig = wallet
-        .sign_transaction(&tx)
-        .await
-        .context("failed to sign transaction")?;
-
-    Ok(EthSignedTxTuple::new(tx, sig))
-}
-
 /// Construct a simple ethereum transfer transaction
 pub async fn construct_transfer_tx(
     rome: &Rome,
     wallet: &LocalWallet,
     chain_id: u64,
 ) -> anyhow::Result<EthSignedTxTuple> {
-    // From wallet
-    let from = wallet.address();
-
     // To address
     let to = TO_ADDRESS.parse::<Address>().unwrap();
 
-    // nonce
-    let nonce = rome
-        .transaction_count(from, chain_id)
-        .context("failed to get transaction count")?;
-
-    // create a legacy transaction request
-    let mut tx = TransactionRequest {
+    // create a legacy transaction request,
+    // nonce, gas and gas price are filled by the builder
+    let tx = TransactionRequest {
         to: Some(to.into()),
-        from: Some(from),
-        nonce: Some(nonce.into()),
         chain_id: Some(chain_id.into()),
-        gas_price: Some(1.into()),
         value: Some(100.into()),
         ..Default::default()
     };
 
-    // estimate gas
-    tx.gas = Some(rome.estimate_gas(&tx).context("failed to estimate gas")?);
-
-    // convert the tx into a legacy tx
-    let tx = TypedTransaction::Legacy(tx);
+    // fill and sign the transaction
+    let tx = rome.rhea_tx_builder(wallet.clone()).build(tx).await?;
 
-    // sign the transaction
-    sign_tx(wallet, tx).await
+    Ok(tx.into_inner())
 }
 
 #[allow(dead_code)]
@@ -2,6 +2,7 @@ mod common;
 
 use std::sync::Arc;
 
+use rome_sdk::rome_solana::signer::SharedSigner;
 use rome_sdk::{Rome, RomeConfig, RomulusTx};
 
 const CHAIN_ID_FIRST: u64 = 200003;
@@ -22,14 +23,14 @@ async fn main() -> anyhow::Result<()> {
     let wallet = common::create_wallet();
     let eth_tx1 = common::construct_transfer_tx(&rome, &wallet, CHAIN_ID_FIRST).await?;
     let eth_tx2 = common::construct_transfer_tx(&rome, &wallet, CHAIN_ID_SECOND).await?;
-    let eth_txs = vec![eth_tx1, eth_tx2];
 
     // Create Solana wallet and transfer instruction
     let sender = common::create_solana_payer();
-    let sol_ixs = vec![common::construct_solana_transfer_ix(&sender)];
-    let signers = vec![Arc::new(sender)];
+    let sol_ix = common::construct_solana_transfer_ix(&sender);
+    let signers: Vec<SharedSigner> = vec![Arc::new(sender)];
 
-    let romulus_tx = RomulusTx::new(eth_txs, sol_ixs);
+    // The steps are executed in order: EVM, SVM, EVM
+    let romulus_tx = RomulusTx::from_steps(vec![eth_tx1.into(), sol_ix.into(), eth_tx2.into()]);
 
     // Compose a cross chain atomic transaction
     let mut rome_tx = rome.compose_cross_chain_tx(romulus_tx, signers).await?;
@@ -1,9 +1,21 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
 
 use anyhow::Context;
+use rome_evm_client::emulator;
 use rome_evm_client::resources::PayerConfig;
+use rome_evm_client::EmulatorPool;
+use rome_solana::compute_units::{ComputeUnitLimit, HeapFrame};
 use rome_solana::config::SolanaConfig;
+use rome_solana::priority_fee::PriorityFee;
+use rome_solana::types::SyncAtomicRpcClient;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+
+/// Prefix of the environment variables overriding the configuration
+pub const ENV_PREFIX: &str = "ROME_";
 
 #[derive(Clone, Default, Debug, serde::Serialize, serde::Deserialize)]
 /// Rome interface configuration
@@ -13,28 +25,326 @@ pub struct RomeConfig {
     pub solana_config: SolanaConfig,
 
     /// Maps chain id to rollup program address
+    #[serde(default)]
     pub rollups: HashMap<u64, String>,
 
-    /// Path to payer key-pair file
+    /// Default payer pool, used by the rollups without their own payers
+    #[serde(default)]
     pub payers: Vec<PayerConfig>,
+
+    /// Maps chain id to the payer pool of the rollup
+    #[serde(default)]
+    pub rollup_payers: HashMap<u64, Vec<PayerConfig>>,
+
+    /// Maps chain id to the gas price (in wei) used to fill transactions,
+    /// required to build transactions with [RheaTxBuilder]
+    ///
+    /// [RheaTxBuilder]: crate::RheaTxBuilder
+    #[serde(default)]
+    pub gas_prices: HashMap<u64, u64>,
+
+    /// Priority fee policy of the Solana transactions
+    #[serde(default)]
+    pub priority_fee: PriorityFee,
+
+    /// Compute unit limit policy of the Solana transactions
+    #[serde(default)]
+    pub compute_unit_limit: ComputeUnitLimit,
+
+    /// Heap frame policy of the Solana transactions
+    #[serde(default)]
+    pub heap_frame: HeapFrame,
 }
 
 impl RomeConfig {
-    /// Load the configuration from the default path
+    /// Payer pool of the rollup: its own payers if configured, the default pool otherwise
+    pub fn payers_for(&self, chain_id: u64) -> anyhow::Result<&[PayerConfig]> {
+        let payers = self
+            .rollup_payers
+            .get(&chain_id)
+            .unwrap_or(&self.payers)
+            .as_slice();
+
+        if payers.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No payers configured for chain_id {}: set rollup_payers.{} or payers",
+                chain_id,
+                chain_id
+            ));
+        }
+
+        Ok(payers)
+    }
+
+    /// Load the configuration from a json file and apply the environment overrides
     pub async fn load_json(path: PathBuf) -> anyhow::Result<Self> {
         let file = tokio::fs::read_to_string(path)
             .await
             .context("Failed to read config file")?;
 
-        serde_json::from_str(&file).context("Failed to parse config file")
+        let mut config: Self =
+            serde_json::from_str(&file).context("Failed to parse config file")?;
+        config.apply_env()?;
+
+        Ok(config)
     }
 
-    /// Load the configuration from the default path
+    /// Load the configuration from a yaml file and apply the environment overrides
     pub async fn load_yml(path: PathBuf) -> anyhow::Result<Self> {
         let file = tokio::fs::read_to_string(path)
             .await
             .context("Failed to read config file")?;
 
-        serde_yaml::from_str(&file).context("Failed to parse config file")
+        let mut config: Self =
+            serde_yaml::from_str(&file).context("Failed to parse config file")?;
+        config.apply_env()?;
+
+        Ok(config)
+    }
+
+    /// Override the fields of the configuration by the `ROME_*` environment variables:
+    ///
+    /// * `ROME_RPC_URL` - url of the Solana RPC
+    /// * `ROME_COMMITMENT` - Solana commitment level
+    /// * `ROME_ROLLUP_<CHAIN_ID>` - rollup program address
+    /// * `ROME_GAS_PRICE_<CHAIN_ID>` - gas price of the rollup
+    /// * `ROME_PAYERS` - default payer pool, json list of payer configs
+    /// * `ROME_ROLLUP_PAYERS_<CHAIN_ID>` - payer pool of the rollup, json list of payer configs
+    /// * `ROME_PRIORITY_FEE` - priority fee policy, json
+    /// * `ROME_COMPUTE_UNIT_LIMIT` - compute unit limit policy, json
+    /// * `ROME_HEAP_FRAME` - heap frame policy, json
+    pub fn apply_env(&mut self) -> anyhow::Result<()> {
+        self.apply_overrides(std::env::vars())
+    }
+
+    fn apply_overrides(
+        &mut self,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> anyhow::Result<()> {
+        for (key, value) in vars {
+            let Some(name) = key.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            let context = || format!("Failed to parse {}", key);
+
+            if name == "RPC_URL" {
+                self.solana_config.rpc_url = value.parse().with_context(context)?;
+            } else if name == "COMMITMENT" {
+                self.solana_config.commitment = value.parse().with_context(context)?;
+            } else if name == "PRIORITY_FEE" {
+                self.priority_fee = serde_json::from_str(&value).with_context(context)?;
+            } else if name == "COMPUTE_UNIT_LIMIT" {
+                self.compute_unit_limit = serde_json::from_str(&value).with_context(context)?;
+            } else if name == "HEAP_FRAME" {
+                self.heap_frame = serde_json::from_str(&value).with_context(context)?;
+            } else if name == "PAYERS" {
+                self.payers = serde_json::from_str(&value).with_context(context)?;
+            } else if let Some(chain_id) = chain_id_of(name, "ROLLUP_PAYERS_") {
+                self.rollup_payers.insert(
+                    chain_id,
+                    serde_json::from_str(&value).with_context(context)?,
+                );
+            } else if let Some(chain_id) = chain_id_of(name, "ROLLUP_") {
+                self.rollups.insert(chain_id, value);
+            } else if let Some(chain_id) = chain_id_of(name, "GAS_PRICE_") {
+                self.gas_prices
+                    .insert(chain_id, value.parse().with_context(context)?);
+            } else {
+                tracing::warn!("Unknown config override {} is ignored", key);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check the configuration against the Solana cluster:
+    ///
+    /// * every rollup program is deployed
+    /// * the chain id is registered in the rollup program
+    /// * payer keypairs can be loaded and have a non-zero balance
+    /// * every payer has holders or fee recipients
+    ///
+    /// Returns an error listing all the problems found
+    pub async fn validate(&self) -> anyhow::Result<()> {
+        let client: RpcClient = self.solana_config.clone().into();
+        let sync_client: SyncAtomicRpcClient = Arc::new(self.solana_config.clone().into());
+        let emulator_pool = EmulatorPool::default();
+        let mut errors = vec![];
+
+        if self.rollups.is_empty() {
+            errors.push("No rollups configured".to_string());
+        }
+
+        for (chain_id, program) in self.rollups.iter() {
+            let sync_client = sync_client.clone();
+            if let Err(e) =
+                validate_rollup(*chain_id, program, &client, sync_client, &emulator_pool).await
+            {
+                errors.push(format!("Rollup {}: {:#}", chain_id, e));
+            }
+
+            let payers = match self.payers_for(*chain_id) {
+                Ok(payers) => payers,
+                Err(e) => {
+                    errors.push(format!("{:#}", e));
+                    continue;
+                }
+            };
+
+            for payer in payers {
+                if let Err(e) = validate_payer(payer, &client).await {
+                    errors.push(format!(
+                        "Rollup {}, payer {}: {:#}",
+                        chain_id,
+                        payer.signer(),
+                        e
+                    ));
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(anyhow::anyhow!("Invalid config:\n{}", errors.join("\n")));
+        }
+
+        Ok(())
+    }
+}
+
+// chain id of the per-rollup override, `None` if the name is not prefixed by a chain id
+fn chain_id_of(name: &str, prefix: &str) -> Option<u64> {
+    name.strip_prefix(prefix)?.parse().ok()
+}
+
+async fn validate_rollup(
+    chain_id: u64,
+    program: &str,
+    client: &RpcClient,
+    sync_client: SyncAtomicRpcClient,
+    emulator_pool: &EmulatorPool,
+) -> anyhow::Result<()> {
+    let program_id = Pubkey::from_str(program).context("Failed to parse program id")?;
+
+    let account = client
+        .get_account(&program_id)
+        .await
+        .with_context(|| format!("Program {} is not reachable", program_id))?;
+    if !account.executable {
+        return Err(anyhow::anyhow!("Account {} is not a program", program_id));
+    }
+
+    // the emulator reads the accounts with the blocking client
+    let rollups = emulator_pool
+        .run(move || Ok(emulator::get_rollups(&program_id, sync_client)?))
+        .await
+        .context("Failed to load registered rollups")?;
+    if !rollups.iter().any(|rollup| rollup.chain == chain_id) {
+        return Err(anyhow::anyhow!(
+            "Chain id {} is not registered in program {}",
+            chain_id,
+            program_id
+        ));
+    }
+
+    Ok(())
+}
+
+async fn validate_payer(payer: &PayerConfig, client: &RpcClient) -> anyhow::Result<()> {
+    payer.check_resources()?;
+
+    let pubkey = payer.signer().signer().await?.pubkey();
+
+    let balance = client
+        .get_balance(&pubkey)
+        .await
+        .context("Failed to get balance")?;
+    if balance == 0 {
+        return Err(anyhow::anyhow!("{} has zero balance", pubkey));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rome_evm_client::resources::SignerConfig;
+    use solana_sdk::commitment_config::CommitmentLevel;
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn env_overrides() {
+        let mut config = RomeConfig::default();
+        config
+            .apply_overrides(vars(&[
+                ("ROME_RPC_URL", "http://solana:8899"),
+                ("ROME_COMMITMENT", "finalized"),
+                (
+                    "ROME_ROLLUP_1001",
+                    "CmobH2vR6aUtQ8x4xd1LYNiH6k2G7PFT5ShEL2Qd5wg4",
+                ),
+                (
+                    "ROME_ROLLUP_PAYERS_1001",
+                    r#"[{"payer_keypair": "/opt/payer.json", "number_holders": 4}]"#,
+                ),
+                ("ROME_GAS_PRICE_1001", "10"),
+                ("PATH", "/usr/bin"),
+            ]))
+            .unwrap();
+
+        assert_eq!(config.solana_config.rpc_url.as_str(), "http://solana:8899/");
+        assert_eq!(config.solana_config.commitment, CommitmentLevel::Finalized);
+        assert_eq!(config.rollups.len(), 1);
+        assert_eq!(config.payers_for(1001).unwrap().len(), 1);
+        assert_eq!(config.gas_prices.get(&1001), Some(&10));
+    }
+
+    #[test]
+    fn remote_signer_payer() {
+        let mut config = RomeConfig::default();
+        config
+            .apply_overrides(vars(&[(
+                "ROME_PAYERS",
+                r#"[{
+                    "remote_signer": {
+                        "url": "http://signer:8080/sign",
+                        "pubkey": "CmobH2vR6aUtQ8x4xd1LYNiH6k2G7PFT5ShEL2Qd5wg4"
+                    },
+                    "number_holders": 4
+                }]"#,
+            )]))
+            .unwrap();
+
+        assert!(matches!(
+            config.payers[0].signer(),
+            SignerConfig::Remote { .. }
+        ));
+    }
+
+    #[test]
+    fn env_override_invalid_chain_id() {
+        let mut config = RomeConfig::default();
+        config
+            .apply_overrides(vars(&[
+                ("ROME_GAS_PRICE_abc", "10"),
+                ("ROME_ROLLUP_DB_URL", "postgres://localhost/rollup"),
+            ]))
+            .unwrap();
+
+        assert!(config.gas_prices.is_empty());
+        assert!(config.rollups.is_empty());
+    }
+
+    #[test]
+    fn env_override_invalid_value() {
+        let mut config = RomeConfig::default();
+        let res = config.apply_overrides(vars(&[("ROME_GAS_PRICE_1001", "ten")]));
+
+        assert!(res.is_err());
     }
 }
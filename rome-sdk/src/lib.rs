@@ -11,9 +11,15 @@ pub use rome_solana;
 pub use rome_utils;
 
 mod config;
+mod middleware;
+mod nonce;
 mod rome;
+mod simulation;
 mod tx;
 
 pub use config::*;
+pub use middleware::*;
+pub use nonce::*;
 pub use rome::*;
+pub use simulation::*;
 pub use tx::*;
@@ -0,0 +1,338 @@
+use crate::tx::{fill_gas_price, to_transaction_request};
+use async_trait::async_trait;
+use ethers::providers::{
+    JsonRpcClient, JsonRpcError, Middleware, MiddlewareError, PendingTransaction, Provider,
+    ProviderError, RpcError,
+};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{
+    Address, Block, BlockId, BlockNumber, Bytes, NameOrAddress, Transaction, TransactionReceipt,
+    TxHash, U256, U64,
+};
+use rome_evm_client::error::RomeEvmError;
+use rome_evm_client::indexer::BlockType;
+use rome_evm_client::RomeEVMClient;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Error of [RomeMiddleware]
+#[derive(Debug, thiserror::Error)]
+pub enum RomeMiddlewareError {
+    /// Error of the underlying provider
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+
+    /// Error of the Rome-EVM client
+    #[error(transparent)]
+    Rome(#[from] RomeEvmError),
+
+    /// Request is not supported by Rome
+    #[error("not supported: {0}")]
+    Unsupported(String),
+}
+
+impl MiddlewareError for RomeMiddlewareError {
+    type Inner = ProviderError;
+
+    fn from_err(src: ProviderError) -> Self {
+        Self::Provider(src)
+    }
+
+    fn as_inner(&self) -> Option<&Self::Inner> {
+        match self {
+            Self::Provider(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Implementation of the ethers [Middleware] on top of [RomeEVMClient].
+///
+/// Lets the ethers contract bindings (abigen) call a rollup directly over Solana,
+/// without a JSON-RPC proxy. Calls, gas estimation, nonces, raw transactions,
+/// blocks and receipts are served by the client. Wrap it into
+/// `SignerMiddleware` to send transactions signed with a local wallet.
+pub struct RomeMiddleware {
+    client: Arc<RomeEVMClient>,
+    provider: Provider<RomeRpcClient>,
+}
+
+impl RomeMiddleware {
+    /// Creates a new [RomeMiddleware]
+    pub fn new(client: Arc<RomeEVMClient>) -> Self {
+        let provider = Provider::new(RomeRpcClient::new(client.clone()));
+
+        Self { client, provider }
+    }
+
+    /// Returns a reference to the Rome-EVM client
+    pub fn client(&self) -> &RomeEVMClient {
+        &self.client
+    }
+
+    fn check_block(&self, block: Option<BlockId>) -> Result<(), RomeMiddlewareError> {
+        match block {
+            None | Some(BlockId::Number(BlockNumber::Latest | BlockNumber::Pending)) => Ok(()),
+            Some(block) => Err(RomeMiddlewareError::Unsupported(format!(
+                "state of block {:?}, only the latest state is available",
+                block
+            ))),
+        }
+    }
+
+    fn resolve(&self, address: NameOrAddress) -> Result<Address, RomeMiddlewareError> {
+        match address {
+            NameOrAddress::Address(address) => Ok(address),
+            NameOrAddress::Name(name) => Err(RomeMiddlewareError::Unsupported(format!(
+                "ENS name {}",
+                name
+            ))),
+        }
+    }
+}
+
+impl Debug for RomeMiddleware {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RomeMiddleware")
+            .field("chain_id", &self.client.chain_id())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Middleware for RomeMiddleware {
+    type Error = RomeMiddlewareError;
+    type Provider = RomeRpcClient;
+    type Inner = Provider<RomeRpcClient>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.provider
+    }
+
+    async fn fill_transaction(
+        &self,
+        tx: &mut TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<(), Self::Error> {
+        if tx.chain_id().is_none() {
+            tx.set_chain_id(self.client.chain_id());
+        }
+
+        fill_gas_price(tx, self.client.gas_price()?);
+
+        if tx.nonce().is_none() {
+            if let Some(from) = tx.from().copied() {
+                let nonce = self.get_transaction_count(from, block).await?;
+                tx.set_nonce(nonce);
+            }
+        }
+
+        if tx.gas().is_none() {
+            let gas = self.estimate_gas(tx, block).await?;
+            tx.set_gas(gas);
+        }
+
+        Ok(())
+    }
+
+    async fn get_block_number(&self) -> Result<U64, Self::Error> {
+        Ok(self.client.block_number().await?)
+    }
+
+    async fn send_raw_transaction<'a>(
+        &'a self,
+        tx: Bytes,
+    ) -> Result<PendingTransaction<'a, Self::Provider>, Self::Error> {
+        let hash = self.client.send_transaction(tx).await?;
+
+        Ok(PendingTransaction::new(hash, self.provider()))
+    }
+
+    async fn get_gas_price(&self) -> Result<U256, Self::Error> {
+        Ok(self.client.gas_price()?)
+    }
+
+    async fn get_chainid(&self) -> Result<U256, Self::Error> {
+        Ok(self.client.chain_id().into())
+    }
+
+    async fn get_transaction_count<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        block: Option<BlockId>,
+    ) -> Result<U256, Self::Error> {
+        self.check_block(block)?;
+        let from = self.resolve(from.into())?;
+
+        Ok(self.client.transaction_count(from).await?.as_u64().into())
+    }
+
+    async fn estimate_gas(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<U256, Self::Error> {
+        self.check_block(block)?;
+
+        Ok(self
+            .client
+            .estimate_gas(&to_transaction_request(tx))
+            .await?)
+    }
+
+    async fn call(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<Bytes, Self::Error> {
+        self.check_block(block)?;
+
+        Ok(self.client.call(&to_transaction_request(tx)).await?)
+    }
+
+    async fn get_block<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Option<Block<TxHash>>, Self::Error> {
+        match self
+            .client
+            .get_block(block_hash_or_number.into(), false)
+            .await?
+        {
+            Some(BlockType::BlockWithHashes(block)) => Ok(Some(block)),
+            Some(BlockType::BlockWithTransactions(_)) => Err(RomeMiddlewareError::Rome(
+                RomeEvmError::Custom("block with transaction hashes expected".to_string()),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_block_with_txs<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Option<Block<Transaction>>, Self::Error> {
+        match self
+            .client
+            .get_block(block_hash_or_number.into(), true)
+            .await?
+        {
+            Some(BlockType::BlockWithTransactions(block)) => Ok(Some(block)),
+            Some(BlockType::BlockWithHashes(_)) => Err(RomeMiddlewareError::Rome(
+                RomeEvmError::Custom("block with full transactions expected".to_string()),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_transaction<T: Send + Sync + Into<TxHash>>(
+        &self,
+        transaction_hash: T,
+    ) -> Result<Option<Transaction>, Self::Error> {
+        Ok(self
+            .client
+            .get_transaction(&transaction_hash.into())
+            .await?)
+    }
+
+    async fn get_transaction_receipt<T: Send + Sync + Into<TxHash>>(
+        &self,
+        transaction_hash: T,
+    ) -> Result<Option<TransactionReceipt>, Self::Error> {
+        Ok(self
+            .client
+            .get_transaction_receipt(&transaction_hash.into())
+            .await?)
+    }
+}
+
+/// Error of [RomeRpcClient]
+#[derive(Debug, thiserror::Error)]
+pub enum RomeRpcClientError {
+    /// Error of the Rome-EVM client
+    #[error(transparent)]
+    Rome(#[from] RomeEvmError),
+
+    /// Failed to encode or decode the request
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+
+    /// Method is not served by the client
+    #[error("method {0} is not supported")]
+    Unsupported(String),
+}
+
+impl RpcError for RomeRpcClientError {
+    fn as_error_response(&self) -> Option<&JsonRpcError> {
+        None
+    }
+
+    fn as_serde_error(&self) -> Option<&serde_json::Error> {
+        match self {
+            Self::Serde(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<RomeRpcClientError> for ProviderError {
+    fn from(src: RomeRpcClientError) -> Self {
+        match src {
+            RomeRpcClientError::Serde(e) => ProviderError::SerdeJson(e),
+            other => ProviderError::JsonRpcClientError(Box::new(other)),
+        }
+    }
+}
+
+/// In-process [JsonRpcClient] backing the provider of [RomeMiddleware].
+///
+/// Serves the requests the ethers [PendingTransaction] polls with
+/// (chain id, block number, transactions and receipts) from [RomeEVMClient].
+pub struct RomeRpcClient {
+    client: Arc<RomeEVMClient>,
+}
+
+impl RomeRpcClient {
+    /// Creates a new [RomeRpcClient]
+    pub fn new(client: Arc<RomeEVMClient>) -> Self {
+        Self { client }
+    }
+}
+
+impl Debug for RomeRpcClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RomeRpcClient")
+            .field("chain_id", &self.client.chain_id())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for RomeRpcClient {
+    type Error = RomeRpcClientError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let params = serde_json::to_value(params)?;
+
+        let result = match method {
+            "eth_chainId" => serde_json::to_value(U64::from(self.client.chain_id()))?,
+            "eth_blockNumber" => serde_json::to_value(self.client.block_number().await?)?,
+            "eth_getTransactionByHash" => {
+                let hash: TxHash = serde_json::from_value(params[0].clone())?;
+                serde_json::to_value(self.client.get_transaction(&hash).await?)?
+            }
+            "eth_getTransactionReceipt" => {
+                let hash: TxHash = serde_json::from_value(params[0].clone())?;
+                serde_json::to_value(self.client.get_transaction_receipt(&hash).await?)?
+            }
+            _ => return Err(RomeRpcClientError::Unsupported(method.to_string())),
+        };
+
+        Ok(serde_json::from_value(result)?)
+    }
+}
@@ -0,0 +1,238 @@
+use ethers::types::Address;
+use rome_evm_client::error::ProgramResult;
+use std::collections::{BTreeSet, HashMap};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Mutex as AsyncMutex;
+
+/// Key of a cached nonce: (chain_id, address)
+type NonceKey = (u64, Address);
+
+#[derive(Default, Debug)]
+struct NonceState {
+    /// Next nonce to hand out. `None` until the first sync with the emulator
+    next: Option<u64>,
+    /// Nonces handed out and not yet confirmed or rolled back
+    in_flight: BTreeSet<u64>,
+    /// Set when the cached value can no longer be trusted
+    needs_resync: bool,
+}
+
+/// Local nonce cache used to pipeline transactions of a single sender.
+///
+/// Nonces are cached per (chain_id, address) and handed out in increasing order
+/// to concurrent callers. The first request for a key and any request following a
+/// detected gap loads the transaction count from the emulator.
+#[derive(Default)]
+pub struct NonceManager {
+    accounts: Mutex<HashMap<NonceKey, Arc<AsyncMutex<NonceState>>>>,
+}
+
+impl NonceManager {
+    /// Create a new instance of [NonceManager]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self, chain_id: u64, address: Address) -> Arc<AsyncMutex<NonceState>> {
+        let mut accounts = self.accounts.lock().unwrap_or_else(|e| e.into_inner());
+
+        accounts.entry((chain_id, address)).or_default().clone()
+    }
+
+    /// Hand out the next nonce for the given account
+    ///
+    /// * `fetch` - loads the transaction count of the account from the chain,
+    ///             called only when the cache is empty or out of sync
+    pub async fn acquire<F, Fut>(
+        &self,
+        chain_id: u64,
+        address: Address,
+        fetch: F,
+    ) -> ProgramResult<u64>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ProgramResult<u64>>,
+    {
+        let state = self.state(chain_id, address);
+        let mut state = state.lock().await;
+
+        let nonce = match state.next {
+            Some(next) if !state.needs_resync => next,
+            _ => {
+                let value = fetch().await?;
+                tracing::debug!(
+                    "nonce of {:?} on chain {} synced: {}",
+                    address,
+                    chain_id,
+                    value
+                );
+                // transactions sent after a gap can not be executed
+                state.in_flight.clear();
+                state.needs_resync = false;
+                value
+            }
+        };
+
+        state.next = Some(nonce + 1);
+        state.in_flight.insert(nonce);
+
+        Ok(nonce)
+    }
+
+    /// Mark the nonce as used by a transaction accepted by the network
+    pub async fn confirm(&self, chain_id: u64, address: Address, nonce: u64) {
+        let state = self.state(chain_id, address);
+        state.lock().await.in_flight.remove(&nonce);
+    }
+
+    /// Return the nonce of a transaction which failed to be sent.
+    ///
+    /// If it is the last nonce handed out, the counter is moved back. Otherwise
+    /// the transactions following it can not be executed, and the next call to
+    /// [NonceManager::acquire] resyncs the counter with the chain.
+    pub async fn rollback(&self, chain_id: u64, address: Address, nonce: u64) {
+        let state = self.state(chain_id, address);
+        let mut state = state.lock().await;
+
+        if !state.in_flight.remove(&nonce) {
+            return;
+        }
+
+        if state.next == Some(nonce + 1) {
+            state.next = Some(nonce);
+        } else {
+            tracing::warn!(
+                "nonce gap detected for {:?} on chain {}: nonce {} rolled back",
+                address,
+                chain_id,
+                nonce
+            );
+            state.needs_resync = true;
+        }
+    }
+
+    /// Reload the transaction count of the account from the chain
+    pub async fn resync<F, Fut>(
+        &self,
+        chain_id: u64,
+        address: Address,
+        fetch: F,
+    ) -> ProgramResult<u64>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ProgramResult<u64>>,
+    {
+        let state = self.state(chain_id, address);
+        let mut state = state.lock().await;
+
+        let value = fetch().await?;
+        state.next = Some(value);
+        state.in_flight.clear();
+        state.needs_resync = false;
+
+        Ok(value)
+    }
+
+    /// Drop the cached nonce of the account
+    pub fn reset(&self, chain_id: u64, address: Address) {
+        let mut accounts = self.accounts.lock().unwrap_or_else(|e| e.into_inner());
+        accounts.remove(&(chain_id, address));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const CHAIN_ID: u64 = 1001;
+
+    #[tokio::test]
+    async fn concurrent_acquire_is_sequential() {
+        let manager = Arc::new(NonceManager::new());
+        let address = Address::random();
+        let fetches = Arc::new(AtomicUsize::new(0));
+
+        let futs = (0..100).map(|_| {
+            let manager = manager.clone();
+            let fetches = fetches.clone();
+            async move {
+                manager
+                    .acquire(CHAIN_ID, address, || async {
+                        fetches.fetch_add(1, Ordering::Relaxed);
+                        Ok(7)
+                    })
+                    .await
+                    .unwrap()
+            }
+        });
+
+        let mut nonces = futures::future::join_all(futs).await;
+        nonces.sort();
+
+        assert_eq!(nonces, (7..107).collect::<Vec<_>>());
+        assert_eq!(fetches.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn rollback_last_nonce() {
+        let manager = NonceManager::new();
+        let address = Address::random();
+
+        let first = manager
+            .acquire(CHAIN_ID, address, || async { Ok(0) })
+            .await
+            .unwrap();
+        let second = manager
+            .acquire(CHAIN_ID, address, || async { unreachable!() })
+            .await
+            .unwrap();
+        manager.rollback(CHAIN_ID, address, second).await;
+
+        let third = manager
+            .acquire(CHAIN_ID, address, || async { unreachable!() })
+            .await
+            .unwrap();
+        assert_eq!((first, second, third), (0, 1, 1));
+    }
+
+    #[tokio::test]
+    async fn gap_triggers_resync() {
+        let manager = NonceManager::new();
+        let address = Address::random();
+
+        for _ in 0..3 {
+            manager
+                .acquire(CHAIN_ID, address, || async { Ok(0) })
+                .await
+                .unwrap();
+        }
+        manager.confirm(CHAIN_ID, address, 0).await;
+        manager.rollback(CHAIN_ID, address, 1).await;
+
+        let nonce = manager
+            .acquire(CHAIN_ID, address, || async { Ok(1) })
+            .await
+            .unwrap();
+        assert_eq!(nonce, 1);
+    }
+
+    #[tokio::test]
+    async fn rollback_only_nonce_moves_counter_back() {
+        let manager = NonceManager::new();
+        let address = Address::random();
+
+        let first = manager
+            .acquire(CHAIN_ID, address, || async { Ok(5) })
+            .await
+            .unwrap();
+        manager.rollback(CHAIN_ID, address, first).await;
+
+        let second = manager
+            .acquire(CHAIN_ID, address, || async { unreachable!() })
+            .await
+            .unwrap();
+        assert_eq!((first, second), (5, 5));
+    }
+}
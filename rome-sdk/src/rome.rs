@@ -1,25 +1,38 @@
-use crate::tx::{RemusTx, RheaTx, RomulusTx};
-use crate::{RomeConfig, RomeTx};
+use crate::tx::{RemusMode, RemusTx, RheaTx, RheaTxBuilder, RomulusStep, RomulusTx};
+use crate::{
+    BundleSimulation, EthSignedTxTuple, LegSimulation, NonceManager, RomeConfig, RomeTx,
+    SolanaSimulation,
+};
+use ethers::providers::Middleware;
+use ethers::signers::Signer;
 use ethers::types::transaction::eip2718::TypedTransaction;
-use ethers::types::{Address, TransactionRequest, U256};
+use ethers::types::{Address, Bytes, TransactionRequest, TxHash, U256, U64};
 use rome_evm_client::error::{ProgramResult, RomeEvmError};
-use rome_evm_client::rome_evm::H160 as EvmH160;
-use rome_evm_client::tx::CrossChainTx;
+use rome_evm_client::rome_evm::{OwnerInfo, H160 as EvmH160, U256 as EvmU256};
 use rome_evm_client::tx::CrossRollupTx;
 use rome_evm_client::tx::TxBuilder;
-use rome_evm_client::util::{check_accounts_len, check_exit_reason, RomeEvmUtil};
-use rome_evm_client::Resource;
+use rome_evm_client::tx::{build_deposit_rlp, deposit_slot, wait_deposit_included, DepositAmount};
+use rome_evm_client::util::{
+    check_accounts_len, check_atomic, check_exit_reason, compute_usage, RomeEvmUtil,
+};
 use rome_evm_client::{emulator, resources::Payer};
+use rome_evm_client::{EmulatorPool, Resource};
 use rome_solana::batch::AdvanceTx;
-use rome_solana::batch::AtomicIxBatch;
+use rome_solana::batch::{AtomicIxBatch, TxVersion};
+use rome_solana::compute_units::{ComputeUsage, MAX_COMPUTE_UNIT_LIMIT, MAX_HEAP_FRAME};
 use rome_solana::indexers::clock::SolanaClockIndexer;
+use rome_solana::observer::TxObserver;
+use rome_solana::signer::{SharedSigner, TxSigner};
 use rome_solana::tower::SolanaTower;
 use rome_solana::types::{AsyncAtomicRpcClient, SyncAtomicRpcClient};
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
 use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
 use solana_sdk::pubkey::Pubkey;
-use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::signature::Signature;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// A centralized structure that manages functionalities of the Rome network
 pub struct Rome {
@@ -29,6 +42,10 @@ pub struct Rome {
     solana: SolanaTower,
     /// Mapping Chai1n_id to corresponding Rome-EVM transaction builder
     rollup_builders: HashMap<u64, TxBuilder>,
+    /// Local nonce cache
+    nonce_manager: NonceManager,
+    /// Mapping chain_id to the gas price of the rollup
+    gas_prices: HashMap<u64, U256>,
 }
 
 impl Rome {
@@ -42,9 +59,17 @@ impl Rome {
             // payer,
             solana,
             rollup_builders,
+            nonce_manager: NonceManager::new(),
+            gas_prices: HashMap::new(),
         }
     }
 
+    /// Set the gas prices of the rollups by chain_id, used to fill transactions
+    pub fn with_gas_prices(mut self, gas_prices: HashMap<u64, U256>) -> Self {
+        self.gas_prices = gas_prices;
+        self
+    }
+
     /// Create a new instance of [Rome] from [RomeConfig]
     /// and start the services
     pub async fn new_with_config(config: RomeConfig) -> anyhow::Result<Self> {
@@ -63,37 +88,45 @@ impl Rome {
         // start the clock
         tokio::spawn(clock_indexer.start());
 
-        let solana = SolanaTower::new(async_rpc_client, clock);
-
-        let payers = Payer::from_config_list(&config.payers).await?;
-        let rollup_builders = config
-            .rollups
-            .into_iter()
-            .map(|(chain_id, rollup_pubkey)| {
-                Pubkey::try_from(rollup_pubkey.as_str())
-                    .map_err(|e| anyhow::anyhow!("Failed to parse program id: {:?}", e))
-                    .map(|program_id| {
-                        (
-                            chain_id,
-                            // TODO: use its own payer list for each rollup
-                            TxBuilder::new(
-                                chain_id,
-                                program_id,
-                                sync_rpc_client.clone(),
-                                payers.clone(),
-                            ),
-                        )
-                    })
-            })
-            .collect::<anyhow::Result<HashMap<_, _>>>()?;
+        let solana = SolanaTower::new(async_rpc_client, clock)
+            .with_priority_fee(config.priority_fee.clone())
+            .with_compute_unit_limit(config.compute_unit_limit.clone())
+            .with_heap_frame(config.heap_frame.clone());
+
+        // the default pool is loaded once and shared by the rollups without their own payers
+        let mut default_payers: Option<Vec<Payer>> = None;
+        let mut rollup_builders = HashMap::new();
+        // the emulations of all rollups share the blocking threads
+        let emulator_pool = EmulatorPool::default();
+
+        for (chain_id, rollup_pubkey) in config.rollups.iter() {
+            let program_id = Pubkey::try_from(rollup_pubkey.as_str())
+                .map_err(|e| anyhow::anyhow!("Failed to parse program id: {:?}", e))?;
+
+            let list = config.payers_for(*chain_id)?;
+            let payers = if config.rollup_payers.contains_key(chain_id) {
+                Payer::from_config_list(list).await?
+            } else {
+                if default_payers.is_none() {
+                    default_payers = Some(Payer::from_config_list(list).await?);
+                }
+                default_payers.clone().unwrap()
+            };
+
+            let builder = TxBuilder::new(*chain_id, program_id, sync_rpc_client.clone(), payers)
+                .with_emulator_pool(emulator_pool.clone());
+            rollup_builders.insert(*chain_id, builder);
+        }
 
         // let payer = SolanaKeyPayer::read_from_file(&config.payer_path).await?;
 
-        Ok(Self {
-            // payer: payer.into_keypair(),
-            solana,
-            rollup_builders,
-        })
+        let gas_prices = config
+            .gas_prices
+            .iter()
+            .map(|(chain_id, gas_price)| (*chain_id, U256::from(*gas_price)))
+            .collect();
+
+        Ok(Self::new(solana, rollup_builders).with_gas_prices(gas_prices))
     }
 
     /// Get the transaction builder for the given chain_id
@@ -119,27 +152,287 @@ impl Rome {
     ///
     /// * `address` - address of account
     /// * `chain_id` - chain id
-    pub fn transaction_count(&self, address: Address, chain_id: u64) -> ProgramResult<u64> {
+    pub async fn transaction_count(&self, address: Address, chain_id: u64) -> ProgramResult<u64> {
         let tx_builder = self.get_transaction_builder(chain_id)?;
 
         // get the program id
-        let program_id = tx_builder.program_id();
+        let program_id = *tx_builder.program_id();
 
         // get the client
         let client = tx_builder.client_cloned();
 
-        // get the transaction count
-        let value =
-            emulator::eth_get_tx_count(program_id, &EvmH160::from(address.0), client, chain_id)?;
+        tx_builder
+            .emulator_pool()
+            .run(move || {
+                Ok(emulator::eth_get_tx_count(
+                    &program_id,
+                    &EvmH160::from(address.0),
+                    client,
+                    chain_id,
+                )?)
+            })
+            .await
+    }
+
+    /// Returns balance of a requested account in the latest block
+    ///
+    /// * `address` - address of account
+    /// * `chain_id` - chain id
+    pub async fn get_balance(&self, address: Address, chain_id: u64) -> ProgramResult<U256> {
+        let tx_builder = self.get_transaction_builder(chain_id)?;
+        let (program_id, client) = (*tx_builder.program_id(), tx_builder.client_cloned());
+
+        let value = tx_builder
+            .emulator_pool()
+            .run(move || {
+                Ok(emulator::eth_get_balance(
+                    &program_id,
+                    &EvmH160::from(address.0),
+                    client,
+                    chain_id,
+                )?)
+            })
+            .await?;
+
+        let mut buf = [0; 32];
+        value.to_big_endian(&mut buf);
+
+        Ok(U256::from_big_endian(&buf))
+    }
+
+    /// Returns bytecode of a requested smart-contract in the latest block
+    ///
+    /// * `address` - address of the smart-contract
+    /// * `chain_id` - chain id
+    pub async fn get_code(&self, address: Address, chain_id: u64) -> ProgramResult<Bytes> {
+        let tx_builder = self.get_transaction_builder(chain_id)?;
+        let (program_id, client) = (*tx_builder.program_id(), tx_builder.client_cloned());
+
+        let value = tx_builder
+            .emulator_pool()
+            .run(move || {
+                Ok(emulator::eth_get_code(
+                    &program_id,
+                    &EvmH160::from(address.0),
+                    client,
+                    chain_id,
+                )?)
+            })
+            .await?;
+
+        Ok(value.into())
+    }
+
+    /// Returns the value of a storage slot of a requested account in the latest block
+    ///
+    /// * `address` - address of account
+    /// * `slot` - index of the storage slot
+    /// * `chain_id` - chain id
+    pub async fn get_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        chain_id: u64,
+    ) -> ProgramResult<U256> {
+        let tx_builder = self.get_transaction_builder(chain_id)?;
+        let (program_id, client) = (*tx_builder.program_id(), tx_builder.client_cloned());
+
+        let mut buf = [0u8; 32];
+        slot.to_big_endian(&mut buf);
+        let slot = EvmU256::from_big_endian(&buf);
+
+        let value = tx_builder
+            .emulator_pool()
+            .run(move || {
+                Ok(emulator::eth_get_storage_at(
+                    &program_id,
+                    &EvmH160::from(address.0),
+                    &slot,
+                    client,
+                    chain_id,
+                )?)
+            })
+            .await?;
+
+        value.to_big_endian(&mut buf);
+
+        Ok(U256::from_big_endian(&buf))
+    }
+
+    /// Returns the rollups registered in the Rome-EVM program of the rollup
+    ///
+    /// * `chain_id` - chain id
+    pub async fn get_rollups(&self, chain_id: u64) -> ProgramResult<Vec<OwnerInfo>> {
+        let tx_builder = self.get_transaction_builder(chain_id)?;
+        let (program_id, client) = (*tx_builder.program_id(), tx_builder.client_cloned());
+
+        tx_builder
+            .emulator_pool()
+            .run(move || Ok(emulator::get_rollups(&program_id, client)?))
+            .await
+    }
+
+    /// Deposit SOLs of the signer to a rollup account, see [RomeEVMClient::deposit].
+    ///
+    /// * `chain_id` - chain id
+    /// * `rlp` - deposit transaction (type 0x7E)
+    /// * `signer` - owner of the deposited SOLs, pays for the Solana transaction
+    ///
+    /// [RomeEVMClient::deposit]: rome_evm_client::RomeEVMClient::deposit
+    pub async fn deposit(
+        &self,
+        chain_id: u64,
+        rlp: &[u8],
+        signer: &dyn TxSigner,
+    ) -> ProgramResult<Signature> {
+        let ix = self
+            .get_transaction_builder(chain_id)?
+            .build_deposit_ix(rlp, &signer.pubkey())
+            .await?;
+
+        self.send_signed_ix(ix, signer).await
+    }
+
+    /// Deposit SOLs of the signer to the rollup account of the recipient,
+    /// see [RomeEVMClient::deposit_to].
+    ///
+    /// * `chain_id` - chain id
+    /// * `recipient` - rollup account the funds are minted on
+    /// * `amount` - amount in lamports, or in wei multiple of 10^9
+    /// * `signer` - owner of the deposited SOLs, pays for the Solana transaction
+    /// * `rollup` - provider of the rollup JSON-RPC, the deposit is looked for in its blocks
+    /// * `timeout` - maximum time to wait for the deposit to be included in a block
+    ///
+    /// Returns the signature of the Solana transaction and the hash of the deposit transaction
+    /// once it is included in a block. The hash is assigned by the indexer of the rollup.
+    ///
+    /// [RomeEVMClient::deposit_to]: rome_evm_client::RomeEVMClient::deposit_to
+    pub async fn deposit_to<M: Middleware>(
+        &self,
+        chain_id: u64,
+        recipient: Address,
+        amount: DepositAmount,
+        signer: &dyn TxSigner,
+        rollup: &M,
+        timeout: Duration,
+    ) -> ProgramResult<(Signature, TxHash)> {
+        let rlp = build_deposit_rlp(recipient, amount)?;
+        let from_block = rollup
+            .get_block_number()
+            .await
+            .map_err(|e| RomeEvmError::Custom(format!("Rollup JSON-RPC error: {}", e)))?
+            + U64::one();
+
+        let signature = self.deposit(chain_id, &rlp, signer).await?;
+        let slot = deposit_slot(self.solana.client(), signature).await?;
+        let hash = wait_deposit_included(
+            rollup,
+            signature,
+            slot,
+            from_block,
+            recipient,
+            amount.to_wei()?,
+            timeout,
+        )
+        .await?;
+
+        Ok((signature, hash))
+    }
+
+    /// Register the owner of a rollup. The rollup must be configured in [RomeConfig].
+    ///
+    /// * `chain_id` - chain id
+    /// * `registry_authority` - registry authority of the Rome-EVM program
+    pub async fn reg_owner(
+        &self,
+        chain_id: u64,
+        registry_authority: &dyn TxSigner,
+    ) -> ProgramResult<Signature> {
+        let ix = self
+            .get_transaction_builder(chain_id)?
+            .build_reg_owner_ix(chain_id, &registry_authority.pubkey())
+            .await?;
+
+        self.send_signed_ix(ix, registry_authority).await
+    }
+
+    // sends the Rome-EVM instruction in a transaction paid and signed by the signer
+    async fn send_signed_ix(
+        &self,
+        ix: Instruction,
+        signer: &dyn TxSigner,
+    ) -> ProgramResult<Signature> {
+        let ix = AtomicIxBatch::new_composible_owned(ix);
+
+        Ok(self
+            .solana
+            .send_and_confirm(&ix, signer, &TxVersion::Legacy)
+            .await?)
+    }
+
+    /// Returns the next nonce of the account from the local nonce cache.
+    /// The cache is synced with [Rome::transaction_count] on first use and after a nonce gap.
+    ///
+    /// The nonce must be released with [Rome::confirm_nonce] or [Rome::rollback_nonce]
+    /// once the transaction is sent.
+    ///
+    /// * `address` - address of account
+    /// * `chain_id` - chain id
+    pub async fn next_nonce(&self, address: Address, chain_id: u64) -> ProgramResult<u64> {
+        self.nonce_manager
+            .acquire(chain_id, address, || {
+                self.transaction_count(address, chain_id)
+            })
+            .await
+    }
+
+    /// Marks the nonce as used by a sent transaction
+    pub async fn confirm_nonce(&self, address: Address, chain_id: u64, nonce: u64) {
+        self.nonce_manager.confirm(chain_id, address, nonce).await
+    }
+
+    /// Returns the nonce of a transaction that failed to be sent
+    pub async fn rollback_nonce(&self, address: Address, chain_id: u64, nonce: u64) {
+        self.nonce_manager.rollback(chain_id, address, nonce).await
+    }
 
-        // convert to U64
-        Ok(value)
+    /// Reloads the cached nonce of the account from the emulator
+    pub async fn resync_nonce(&self, address: Address, chain_id: u64) -> ProgramResult<u64> {
+        self.nonce_manager
+            .resync(chain_id, address, || {
+                self.transaction_count(address, chain_id)
+            })
+            .await
+    }
+
+    /// Get the local nonce cache
+    pub fn nonce_manager(&self) -> &NonceManager {
+        &self.nonce_manager
+    }
+
+    /// Returns the gas price of the rollup configured in [RomeConfig]
+    ///
+    /// * `chain_id` - chain id
+    pub fn gas_price(&self, chain_id: u64) -> ProgramResult<U256> {
+        self.get_transaction_builder(chain_id)?;
+
+        self.gas_prices.get(&chain_id).cloned().ok_or_else(|| {
+            RomeEvmError::Custom(format!(
+                "Gas price of rollup {} is not configured",
+                chain_id
+            ))
+        })
+    }
+
+    /// Create a [RheaTxBuilder] signing transactions with the given signer
+    pub fn rhea_tx_builder<S: Signer>(&self, signer: S) -> RheaTxBuilder<'_, S> {
+        RheaTxBuilder::new(self, signer)
     }
 
     /// Estimate gas amount for a given transaction
     ///
     /// * `tx` - transaction request to estimate gas
-    pub fn estimate_gas(&self, tx: &TransactionRequest) -> ProgramResult<U256> {
+    pub async fn estimate_gas(&self, tx: &TransactionRequest) -> ProgramResult<U256> {
         // get the chain id
         let Some(chain_id) = tx.chain_id else {
             return Err(RomeEvmError::NoChainId);
@@ -149,16 +442,16 @@ impl Rome {
         let tx_builder = self.get_transaction_builder(chain_id.as_u64())?;
 
         // get the program id
-        let program_id = tx_builder.program_id();
+        let program_id = *tx_builder.program_id();
 
         // get the client
         let client = tx_builder.client_cloned();
 
-        let emulation = emulator::eth_estimate_gas(
-            program_id,
-            RomeEvmUtil::cast_transaction_request(tx, tx_builder.chain_id),
-            client,
-        )?;
+        let call = RomeEvmUtil::cast_transaction_request(tx, tx_builder.chain_id);
+        let emulation = tx_builder
+            .emulator_pool()
+            .run(move || Ok(emulator::eth_estimate_gas(&program_id, call, client)?))
+            .await?;
 
         check_exit_reason(&emulation)?;
         check_accounts_len(&emulation)?;
@@ -168,8 +461,8 @@ impl Rome {
 
     /// Compose a simple rollup transaction
     pub async fn compose_rollup_tx<'a>(&self, tx: RheaTx<'a>) -> ProgramResult<RomeTx<'a>> {
-        println!("\nCompose rollup tx\n");
-        println!("Transaction {:?}", tx.tx());
+        tracing::debug!("Compose rollup tx");
+        tracing::debug!("Transaction {:?}", tx.tx());
 
         // get the transaction builder
         let builder = self.get_transaction_builder_for_tx(tx.tx())?;
@@ -182,18 +475,19 @@ impl Rome {
         builder.build_tx(rlp, hash).await
     }
 
-    /// Compose a cross rollup transaction
+    /// Compose a cross rollup transaction executed atomically in a single Solana transaction.
+    ///
+    /// Fails with [RomeEvmError::NonAtomicTx] reporting the first transaction which
+    /// does not fit into a single Solana transaction.
     pub async fn compose_cross_rollup_tx<'a>(&self, _tx: RemusTx<'a>) -> ProgramResult<RomeTx> {
-        println!("\nCompose cross rollup tx\n");
+        tracing::debug!("Compose cross rollup tx");
 
-        let mut instructions = vec![
-            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
-            ComputeBudgetInstruction::request_heap_frame(256 * 1024),
-        ];
+        let mut instructions = compute_budget_ixs();
+        let mut usage = ComputeUsage::default();
         let mut resource: Option<Arc<Resource>> = None;
 
-        for tx in _tx.iter() {
-            println!("Transaction {:?}", tx);
+        for (index, tx) in _tx.iter().enumerate() {
+            tracing::debug!("Transaction {:?}", tx);
 
             let builder = self.get_transaction_builder_for_tx(tx.tx())?;
             let current_resource = builder.lock_resource().await?;
@@ -201,21 +495,15 @@ impl Rome {
                 resource = Some(current_resource);
             }
 
-            let rlp = tx.signed_rlp_bytes();
-
-            let mut data = vec![emulator::Instruction::DoTx as u8];
-            data.append(&mut resource.as_ref().unwrap().fee_recipient());
-            data.extend_from_slice(rlp.as_ref());
-            let emulation = builder.emulate(&data, &resource.as_ref().unwrap().payer_key())?;
-
-            if !emulation.is_atomic {
-                return Err(RomeEvmError::Custom(
-                    "Transaction is too large or expensive".to_string(),
-                ));
-            }
+            let data = bundle_tx_data(resource.as_ref().unwrap(), tx);
+            let emulation = builder
+                .emulate(&data, &resource.as_ref().unwrap().payer_key())
+                .await?;
+            check_atomic(&emulation, index)?;
+            usage = usage.join(compute_usage(&emulation));
 
             let ix = builder.build_ix(&emulation, data);
-            println!("Instruction {:?}", ix);
+            tracing::debug!("Instruction {:?}", ix);
             instructions.push(ix);
         }
 
@@ -224,65 +512,225 @@ impl Rome {
         })?;
 
         Ok(Box::new(CrossRollupTx::new(
-            AtomicIxBatch::new_owned(instructions),
+            AtomicIxBatch::new_owned(instructions).with_usage(usage),
             resource.payer(),
         )))
     }
 
-    /// Compose a cross chain transaction
+    /// Compose a cross chain transaction executing the steps in order in a single Solana transaction.
+    ///
+    /// Each Ethereum transaction is emulated on the current state of its rollup, without the
+    /// effects of the preceding steps. Fails with [RomeEvmError::NonAtomicTx] reporting the
+    /// index of the first step which does not fit into a single Solana transaction.
     pub async fn compose_cross_chain_tx<'a>(
         &self,
         romulus_tx: RomulusTx<'a>,
-        signers: Vec<Arc<Keypair>>,
+        signers: Vec<SharedSigner>,
     ) -> ProgramResult<RomeTx> {
-        println!("\nCompose cross chain tx\n");
+        tracing::debug!("Compose cross chain tx");
+
+        // the usage of the Solana instructions is unknown, the requested budget is kept
+        let mut instructions = compute_budget_ixs();
+        // every EVM transaction is emulated with the payer of the Solana transaction,
+        // the resource of the first one
+        let mut locked: Option<(Arc<Resource>, &TxBuilder)> = None;
+
+        for (index, step) in romulus_tx.steps().iter().enumerate() {
+            match step {
+                RomulusStep::Evm(tx) => {
+                    tracing::debug!("Eth Transaction {:?}", tx);
+
+                    let builder = self.get_transaction_builder_for_tx(tx.tx())?;
+                    if locked.is_none() {
+                        locked = Some((builder.lock_resource().await?, builder));
+                    }
+                    let (resource, _) = locked.as_ref().unwrap();
+
+                    let data = bundle_tx_data(resource, tx);
+                    let emulation = builder.emulate(&data, &resource.payer_key()).await?;
+                    check_atomic(&emulation, index)?;
+                    let ix = builder.build_ix(&emulation, data);
+                    tracing::debug!("Instruction {:?}", ix);
+                    instructions.push(ix);
+                }
+                RomulusStep::Svm(ix) => {
+                    tracing::debug!("Sol Instruction {:?}", ix);
+
+                    instructions.push(ix.clone());
+                }
+            }
+        }
 
-        let mut instructions = vec![
-            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
-            ComputeBudgetInstruction::request_heap_frame(256 * 1024),
-        ];
-        let mut resource: Option<Arc<Resource>> = None;
+        let (resource, builder) = locked.ok_or_else(|| {
+            RomeEvmError::Custom("Failed to acquire resource for Solana transaction".to_string())
+        })?;
+
+        builder.compose_cross_chain_tx(AtomicIxBatch::new_owned(instructions), resource, signers)
+    }
+
+    /// Simulate a cross rollup transaction without sending it.
+    ///
+    /// Every rollup transaction is emulated on the current state of its rollup,
+    /// then the composed Solana transaction is simulated by the Solana node.
+    pub async fn simulate_cross_rollup_tx(
+        &self,
+        tx: &RemusTx<'_>,
+    ) -> ProgramResult<BundleSimulation> {
+        let steps = tx.iter().cloned().map(RomulusStep::Evm).collect::<Vec<_>>();
+        self.simulate_bundle(&steps).await
+    }
+
+    /// Simulate a cross chain transaction without sending it.
+    ///
+    /// The signatures of the Solana signers are not verified, so the bundle
+    /// can be simulated before they sign it.
+    pub async fn simulate_cross_chain_tx(
+        &self,
+        tx: &RomulusTx<'_>,
+    ) -> ProgramResult<BundleSimulation> {
+        self.simulate_bundle(tx.steps()).await
+    }
 
-        for tx in romulus_tx.eth_txs().iter() {
-            println!("Eth Transaction {:?}", tx);
+    async fn simulate_bundle(&self, steps: &[RomulusStep]) -> ProgramResult<BundleSimulation> {
+        let mut instructions = vec![];
+        let mut resource: Option<Arc<Resource>> = None;
+        let mut legs = vec![];
+        // usage of the SVM instructions is unknown
+        let mut usage = Some(ComputeUsage::default());
+
+        for step in steps {
+            let tx = match step {
+                RomulusStep::Evm(tx) => tx,
+                RomulusStep::Svm(ix) => {
+                    instructions.push(ix.clone());
+                    usage = None;
+                    continue;
+                }
+            };
 
             let builder = self.get_transaction_builder_for_tx(tx.tx())?;
-            let current_resource = builder.lock_resource().await?;
             if resource.is_none() {
-                resource = Some(current_resource);
+                resource = Some(builder.lock_resource().await?);
             }
+            let resource = resource.as_ref().unwrap();
+
+            // the exit reason is reported, not checked
+            let data = bundle_tx_data(resource, tx);
+            let (program_id, client) = (*builder.program_id(), builder.client_cloned());
+            let (bin, payer) = (data.clone(), resource.payer_key());
+            let emulation = builder
+                .emulator_pool()
+                .run(move || Ok(emulator::emulate(&program_id, &bin, &payer, client)?))
+                .await?;
+
+            legs.push(LegSimulation::new(
+                builder.chain_id,
+                tx.tx().hash(tx.sig()),
+                &emulation,
+                instructions.len(),
+            ));
+            usage = usage.map(|usage| usage.join(compute_usage(&emulation)));
+            instructions.push(builder.build_ix(&emulation, data));
+        }
 
-            let rlp = tx.signed_rlp_bytes();
+        let resource = resource.ok_or_else(|| {
+            RomeEvmError::Custom("Failed to acquire resource for Solana transaction".to_string())
+        })?;
 
-            let mut data = vec![emulator::Instruction::DoTx as u8];
-            data.append(&mut resource.as_ref().unwrap().fee_recipient());
-            data.extend_from_slice(rlp.as_ref());
-            let emulation = builder.emulate(&data, &resource.as_ref().unwrap().payer_key())?;
+        let bundle_ixs = instructions.len();
+        let mut batch = AtomicIxBatch::new_owned([compute_budget_ixs(), instructions].concat());
+        if let Some(usage) = usage {
+            batch = batch.with_usage(usage);
+        }
+        let tx = self
+            .solana
+            .to_unsigned_tx(&batch, &resource.payer_key())
+            .await?;
+        // the compute budget instructions precede the instructions of the bundle
+        let budget_ixs = tx.message.instructions().len() - bundle_ixs;
+
+        // signatures are not verified, the node replaces the blockhash by the latest one
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            ..Default::default()
+        };
+        let result = self
+            .solana
+            .client()
+            .simulate_transaction_with_config(&tx, config)
+            .await?
+            .value;
+
+        let solana = SolanaSimulation::new(
+            result.err,
+            result.units_consumed,
+            result.logs.unwrap_or_default(),
+        )?;
 
-            if !emulation.is_atomic {
-                return Err(RomeEvmError::Custom(
-                    "Transaction is too large or expensive".to_string(),
-                ));
+        Ok(BundleSimulation::new(legs, budget_ixs, solana))
+    }
+
+    /// Execute a cross rollup transaction in the given [RemusMode]
+    ///
+    /// Returns the signatures of the last Solana transaction of each executed step:
+    /// a single signature for an atomic execution, one per rollup transaction otherwise.
+    pub async fn send_cross_rollup_tx(
+        &self,
+        tx: RemusTx<'_>,
+        mode: RemusMode,
+    ) -> anyhow::Result<Vec<Signature>> {
+        match mode {
+            RemusMode::Atomic => {
+                let mut rome_tx = self.compose_cross_rollup_tx(tx).await?;
+                Ok(vec![self.send_and_confirm(&mut *rome_tx).await?])
+            }
+            RemusMode::Sequential => self.send_cross_rollup_tx_sequential(&tx).await,
+            RemusMode::AtomicOrSequential => {
+                match self.compose_cross_rollup_tx(RemusTx::from_ref(&tx)).await {
+                    Ok(mut rome_tx) => Ok(vec![self.send_and_confirm(&mut *rome_tx).await?]),
+                    Err(RomeEvmError::NonAtomicTx(index, iterations, units, accounts)) => {
+                        tracing::warn!(
+                            "Transaction #{} can not be executed atomically ({} iterations, {} compute units per iteration, {} accounts), falling back to sequential execution",
+                            index,
+                            iterations,
+                            units,
+                            accounts
+                        );
+                        self.send_cross_rollup_tx_sequential(&tx).await
+                    }
+                    Err(e) => Err(e.into()),
+                }
             }
-            let ix = builder.build_ix(&emulation, data);
-            println!("Instruction {:?}", ix);
-            instructions.push(ix);
         }
-        for ix in romulus_tx.sol_ixs().iter() {
-            println!("Sol Instruction {:?}", ix);
+    }
 
-            instructions.push(ix.clone());
+    async fn send_cross_rollup_tx_sequential(
+        &self,
+        tx: &RemusTx<'_>,
+    ) -> anyhow::Result<Vec<Signature>> {
+        let mut sigs = Vec::with_capacity(tx.len());
+
+        for (index, leg) in tx.iter().enumerate() {
+            // every rollup transaction is composed right before it's sent,
+            // so the emulation takes into account the state changed by the previous ones
+            let result = match self.compose_rollup_tx(RheaTx::from_ref(leg)).await {
+                Ok(mut rome_tx) => self.send_and_confirm(&mut *rome_tx).await,
+                Err(e) => Err(e.into()),
+            };
+
+            match result {
+                Ok(sig) => sigs.push(sig),
+                Err(e) => {
+                    return Err(e.context(format!(
+                        "Transaction #{} of the cross rollup transaction failed, {} preceding transactions are committed",
+                        index, index
+                    )))
+                }
+            }
         }
 
-        let resource = resource.ok_or_else(|| {
-            RomeEvmError::Custom("Failed to acquire resource for Solana transaction".to_string())
-        })?;
-
-        Ok(Box::new(CrossChainTx::new(
-            AtomicIxBatch::new_owned(instructions),
-            resource.payer(),
-            signers,
-        )))
+        Ok(sigs)
     }
 
     /// Send and confirm
@@ -290,7 +738,7 @@ impl Rome {
         &self,
         tx: &mut dyn AdvanceTx<'_, Error = RomeEvmError>,
     ) -> anyhow::Result<Signature> {
-        println!("\nsend_and_confirm\n");
+        tracing::debug!("send_and_confirm");
 
         Ok(self
             .solana
@@ -305,4 +753,26 @@ impl Rome {
     pub fn solana(&self) -> &SolanaTower {
         &self.solana
     }
+
+    /// Register an observer of the execution of transactions sent by [Rome]
+    pub fn register_observer(&self, observer: Arc<dyn TxObserver>) {
+        self.solana.register_observer(observer);
+    }
+}
+
+// compute budget requested by the Solana transaction of a bundle
+fn compute_budget_ixs() -> Vec<Instruction> {
+    vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNIT_LIMIT),
+        ComputeBudgetInstruction::request_heap_frame(MAX_HEAP_FRAME),
+    ]
+}
+
+// data of the Rome-EVM instruction executing the transaction of a bundle
+fn bundle_tx_data(resource: &Resource, tx: &EthSignedTxTuple) -> Vec<u8> {
+    let mut data = vec![emulator::Instruction::DoTx as u8];
+    data.append(&mut resource.fee_recipient());
+    data.extend_from_slice(tx.signed_rlp_bytes().as_ref());
+
+    data
 }
@@ -0,0 +1,240 @@
+use ethers::types::{Bytes, Log, TxHash, U256};
+use rome_evm_client::emulator::Emulation;
+use rome_evm_client::error::ProgramResult;
+use rome_evm_client::indexer::parsers::log_parser::{self, LogParser};
+use rome_evm_client::rome_evm::ExitReason;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::transaction::TransactionError;
+
+/// Emulation result of a single EVM transaction of a bundle
+#[derive(Clone, Debug)]
+pub struct LegSimulation {
+    /// Chain id of the rollup
+    pub chain_id: u64,
+    /// Hash of the EVM transaction
+    pub hash: TxHash,
+    /// Exit reason of the EVM, `None` if the VM was not started
+    pub exit_reason: Option<ExitReason>,
+    /// Gas used by the transaction
+    pub gas: U256,
+    /// Solana accounts touched by the transaction
+    pub accounts: Vec<AccountMeta>,
+    /// Data returned by the transaction
+    pub return_data: Option<Bytes>,
+    /// Whether the transaction fits into a single Solana transaction
+    pub is_atomic: bool,
+    /// Index of the top-level instruction executing the transaction in the Solana transaction
+    pub instruction: usize,
+}
+
+impl LegSimulation {
+    /// Creates a new [LegSimulation] from the emulation report
+    ///
+    /// * `instruction` - index of the instruction executing the transaction in the bundle
+    pub fn new(chain_id: u64, hash: TxHash, emulation: &Emulation, instruction: usize) -> Self {
+        let accounts = emulation
+            .accounts
+            .iter()
+            .map(|(pubkey, item)| AccountMeta {
+                pubkey: *pubkey,
+                is_signer: item.signer,
+                is_writable: item.account.writable,
+            })
+            .collect();
+
+        let vm = emulation.vm.as_ref();
+
+        Self {
+            chain_id,
+            hash,
+            exit_reason: vm.map(|vm| vm.exit_reason.clone()),
+            gas: emulation.gas.into(),
+            accounts,
+            return_data: vm.and_then(|vm| vm.return_value.clone()).map(Bytes::from),
+            is_atomic: emulation.is_atomic,
+            instruction,
+        }
+    }
+
+    /// Whether the EVM transaction succeeded
+    pub fn is_success(&self) -> bool {
+        matches!(self.exit_reason, None | Some(ExitReason::Succeed(_)))
+    }
+}
+
+/// Logs of a top-level instruction of the simulated Solana transaction
+#[derive(Clone, Debug, Default)]
+pub struct InstructionLogs {
+    /// Raw program logs, including the logs of the inner instructions
+    pub logs: Vec<String>,
+    /// EVM events emitted by the instruction
+    pub events: Vec<Log>,
+    /// EVM exit reason reported by the instruction
+    pub exit_reason: Option<log_parser::ExitReason>,
+}
+
+impl InstructionLogs {
+    /// Decodes the Rome-EVM events and exit reason from the program logs
+    pub fn decode(logs: Vec<String>) -> ProgramResult<Self> {
+        let mut parser = LogParser::new();
+        parser.parse(&logs)?;
+
+        Ok(Self {
+            logs,
+            events: parser.events,
+            exit_reason: parser.exit_reason,
+        })
+    }
+}
+
+/// Result of the Solana simulation of a composed bundle
+#[derive(Clone, Debug, Default)]
+pub struct SolanaSimulation {
+    /// Error of the Solana transaction, `None` if it succeeded
+    pub err: Option<TransactionError>,
+    /// Compute units consumed by the transaction
+    pub units_consumed: Option<u64>,
+    /// Logs of the top-level instructions, in order of execution.
+    /// Instructions not executed due to an error are missing.
+    pub instructions: Vec<InstructionLogs>,
+}
+
+impl SolanaSimulation {
+    /// Creates a new [SolanaSimulation] from the result of the `simulateTransaction` call
+    pub fn new(
+        err: Option<TransactionError>,
+        units_consumed: Option<u64>,
+        logs: Vec<String>,
+    ) -> ProgramResult<Self> {
+        let instructions = split_instruction_logs(logs)
+            .into_iter()
+            .map(InstructionLogs::decode)
+            .collect::<ProgramResult<Vec<_>>>()?;
+
+        Ok(Self {
+            err,
+            units_consumed,
+            instructions,
+        })
+    }
+}
+
+/// Dry-run result of a bundle of transactions
+#[derive(Clone, Debug)]
+pub struct BundleSimulation {
+    /// Emulation results of the EVM transactions, in order
+    pub legs: Vec<LegSimulation>,
+    /// Simulation of the composed Solana transaction
+    pub solana: SolanaSimulation,
+}
+
+impl BundleSimulation {
+    /// Creates a new [BundleSimulation]
+    ///
+    /// * `budget_ixs` - number of the compute budget instructions
+    ///   preceding the instructions of the bundle in the Solana transaction
+    pub fn new(mut legs: Vec<LegSimulation>, budget_ixs: usize, solana: SolanaSimulation) -> Self {
+        for leg in &mut legs {
+            leg.instruction += budget_ixs;
+        }
+
+        Self { legs, solana }
+    }
+
+    /// Logs of the instruction executing the EVM transaction,
+    /// `None` if the instruction was not executed
+    pub fn leg_logs(&self, leg: usize) -> Option<&InstructionLogs> {
+        self.solana
+            .instructions
+            .get(self.legs.get(leg)?.instruction)
+    }
+
+    /// Whether every EVM transaction succeeded and the Solana transaction can be executed
+    pub fn is_success(&self) -> bool {
+        self.solana.err.is_none() && self.legs.iter().all(LegSimulation::is_success)
+    }
+}
+
+// splits the logs of a Solana transaction by top-level instruction
+fn split_instruction_logs(logs: Vec<String>) -> Vec<Vec<String>> {
+    let mut instructions: Vec<Vec<String>> = vec![];
+
+    for log in logs {
+        let top_level = log.starts_with("Program ") && log.ends_with(" invoke [1]");
+        match instructions.last_mut() {
+            Some(current) if !top_level => current.push(log),
+            _ => instructions.push(vec![log]),
+        }
+    }
+
+    instructions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_logs_by_top_level_instruction() {
+        let logs = [
+            "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+            "Program ComputeBudget111111111111111111111111111111 success",
+            "Program RomeEvm111111111111111111111111111111111111 invoke [1]",
+            "Program log: Instruction: Transfer",
+            "Program 11111111111111111111111111111111 invoke [2]",
+            "Program 11111111111111111111111111111111 success",
+            "Program RomeEvm111111111111111111111111111111111111 success",
+        ]
+        .map(String::from)
+        .to_vec();
+
+        let instructions = split_instruction_logs(logs);
+
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[0].len(), 2);
+        assert_eq!(instructions[1].len(), 5);
+    }
+
+    fn leg(instruction: usize) -> LegSimulation {
+        LegSimulation {
+            chain_id: 1001,
+            hash: TxHash::random(),
+            exit_reason: None,
+            gas: U256::zero(),
+            accounts: vec![],
+            return_data: None,
+            is_atomic: true,
+            instruction,
+        }
+    }
+
+    #[test]
+    fn legs_are_mapped_to_instruction_logs() {
+        let executed = |program: &str| InstructionLogs {
+            logs: vec![format!("Program {} invoke [1]", program)],
+            ..Default::default()
+        };
+        // compute budget, leg #0, svm instruction; leg #1 is not executed
+        let solana = SolanaSimulation {
+            err: Some(TransactionError::AccountNotFound),
+            units_consumed: Some(1_000),
+            instructions: vec![
+                executed("ComputeBudget111111111111111111111111111111"),
+                executed("RomeEvm111111111111111111111111111111111111"),
+                executed("11111111111111111111111111111111"),
+            ],
+        };
+
+        let bundle = BundleSimulation::new(vec![leg(0), leg(2)], 1, solana);
+
+        assert_eq!(bundle.legs[0].instruction, 1);
+        assert_eq!(bundle.legs[1].instruction, 3);
+        assert_eq!(
+            bundle.leg_logs(0).unwrap().logs[0],
+            "Program RomeEvm111111111111111111111111111111111111 invoke [1]"
+        );
+        assert!(bundle.leg_logs(1).is_none());
+        assert!(bundle.leg_logs(2).is_none());
+        assert!(!bundle.is_success());
+    }
+}
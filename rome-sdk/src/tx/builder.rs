@@ -0,0 +1,164 @@
+use super::{EthSignedTxTuple, RheaTx};
+use crate::Rome;
+use anyhow::Context;
+use ethers::signers::Signer;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{TransactionRequest, U256};
+use rome_evm_client::error::RomeEvmError;
+
+/// Builder of ready to send [RheaTx].
+///
+/// Fills the missing fields of a transaction request (sender, nonce, gas price and gas limit),
+/// signs it with the given ethers [Signer] and wraps it into a [RheaTx].
+/// Legacy, EIP-2930 and EIP-1559 requests are supported.
+pub struct RheaTxBuilder<'a, S> {
+    rome: &'a Rome,
+    signer: S,
+    use_nonce_manager: bool,
+}
+
+impl<'a, S: Signer> RheaTxBuilder<'a, S> {
+    /// Creates a new [RheaTxBuilder]
+    pub fn new(rome: &'a Rome, signer: S) -> Self {
+        Self {
+            rome,
+            signer,
+            use_nonce_manager: false,
+        }
+    }
+
+    /// Take nonces from the local nonce cache of [Rome] instead of the emulator.
+    ///
+    /// The caller is responsible to release the nonce with [Rome::confirm_nonce]
+    /// or [Rome::rollback_nonce] once the transaction is sent.
+    pub fn with_nonce_manager(mut self, enable: bool) -> Self {
+        self.use_nonce_manager = enable;
+        self
+    }
+
+    /// Returns a reference to the signer
+    pub fn signer(&self) -> &S {
+        &self.signer
+    }
+
+    /// Fills the missing fields of the transaction request.
+    ///
+    /// * chain id - must be set by the caller, the chain id of the signer is not used
+    ///              as it defaults to mainnet
+    /// * sender - the address of the signer
+    /// * nonce - [Rome::transaction_count] or [Rome::next_nonce]
+    /// * gas price - [Rome::gas_price] if not set, zero priority fee for EIP-1559 requests
+    /// * gas limit - [Rome::estimate_gas]
+    pub async fn fill(&self, tx: impl Into<TypedTransaction>) -> anyhow::Result<TypedTransaction> {
+        let mut tx: TypedTransaction = tx.into();
+
+        if let TypedTransaction::DepositTransaction(_) = tx {
+            return Err(anyhow::anyhow!(
+                "deposit transactions can not be built by RheaTxBuilder"
+            ));
+        }
+
+        let Some(chain_id) = tx.chain_id().map(|chain_id| chain_id.as_u64()) else {
+            return Err(RomeEvmError::NoChainId.into());
+        };
+        // check the rollup is supported
+        self.rome.get_transaction_builder(chain_id)?;
+
+        let from = tx.from().copied().unwrap_or_else(|| self.signer.address());
+        tx.set_from(from);
+
+        let gas_price = match tx.gas_price() {
+            Some(gas_price) => gas_price,
+            None => self.rome.gas_price(chain_id)?,
+        };
+        fill_gas_price(&mut tx, gas_price);
+
+        if tx.nonce().is_none() {
+            let nonce = if self.use_nonce_manager {
+                self.rome.next_nonce(from, chain_id).await?
+            } else {
+                self.rome
+                    .transaction_count(from, chain_id)
+                    .await
+                    .context("failed to get transaction count")?
+            };
+            tx.set_nonce(nonce);
+        }
+
+        if tx.gas().is_none() {
+            match self.rome.estimate_gas(&to_transaction_request(&tx)).await {
+                Ok(gas) => {
+                    tx.set_gas(gas);
+                }
+                Err(e) => {
+                    self.release_nonce(&tx).await;
+                    return Err(anyhow::Error::new(e).context("failed to estimate gas"));
+                }
+            }
+        }
+
+        Ok(tx)
+    }
+
+    /// Fills the missing fields of the transaction request, signs it and wraps it into a [RheaTx]
+    pub async fn build(&self, tx: impl Into<TypedTransaction>) -> anyhow::Result<RheaTx<'static>> {
+        let tx = self.fill(tx).await?;
+
+        let sig = match self.signer.sign_transaction(&tx).await {
+            Ok(sig) => sig,
+            Err(e) => {
+                self.release_nonce(&tx).await;
+                return Err(anyhow::Error::new(e).context("failed to sign transaction"));
+            }
+        };
+
+        Ok(RheaTx::new(EthSignedTxTuple::new(tx, sig)))
+    }
+
+    // returns the nonce to the local cache if the transaction can not be built
+    async fn release_nonce(&self, tx: &TypedTransaction) {
+        if !self.use_nonce_manager {
+            return;
+        }
+
+        if let (Some(from), Some(chain_id), Some(nonce)) = (tx.from(), tx.chain_id(), tx.nonce()) {
+            self.rome
+                .rollback_nonce(*from, chain_id.as_u64(), nonce.as_u64())
+                .await;
+        }
+    }
+}
+
+/// Converts the typed transaction into the request accepted by the emulator
+pub(crate) fn to_transaction_request(tx: &TypedTransaction) -> TransactionRequest {
+    TransactionRequest {
+        from: tx.from().copied(),
+        to: tx.to().cloned(),
+        gas: tx.gas().cloned(),
+        gas_price: tx.gas_price(),
+        value: tx.value().cloned(),
+        data: tx.data().cloned(),
+        nonce: tx.nonce().cloned(),
+        chain_id: tx.chain_id(),
+    }
+}
+
+/// Sets the gas price of the transaction if not set.
+///
+/// Rome-EVM charges the flat gas price, so EIP-1559 transactions pay no priority fee.
+pub(crate) fn fill_gas_price(tx: &mut TypedTransaction, gas_price: U256) {
+    match tx {
+        TypedTransaction::Legacy(request) => {
+            request.gas_price.get_or_insert(gas_price);
+        }
+        TypedTransaction::Eip2930(request) => {
+            request.tx.gas_price.get_or_insert(gas_price);
+        }
+        TypedTransaction::Eip1559(request) => {
+            request.max_fee_per_gas.get_or_insert(gas_price);
+            request.max_priority_fee_per_gas.get_or_insert(U256::zero());
+        }
+        // deposits are not paid by the sender
+        TypedTransaction::DepositTransaction(_) => {}
+    }
+}
@@ -1,8 +1,10 @@
+mod builder;
 mod remus;
 mod rhea;
 mod romulus;
 mod tx_tuple;
 
+pub use builder::*;
 pub use remus::*;
 pub use rhea::*;
 use rome_evm_client::error::RomeEvmError;
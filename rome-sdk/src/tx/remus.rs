@@ -26,3 +26,24 @@ impl<'a> RemusTx<'a> {
         Self(Cow::Borrowed(tx))
     }
 }
+
+/// Execution mode of a [RemusTx]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RemusMode {
+    /// All transactions are executed in a single Solana transaction: either all of them
+    /// are committed or none. Fails with [NonAtomicTx] if any transaction does not fit
+    /// into a single Solana transaction.
+    ///
+    /// [NonAtomicTx]: rome_evm_client::error::RomeEvmError::NonAtomicTx
+    #[default]
+    Atomic,
+    /// Transactions are executed one by one, in order, each one as a separate rollup
+    /// transaction (atomic, iterative or using a holder account, as required).
+    ///
+    /// Each transaction is atomic on its own rollup, the bundle is not: when a transaction
+    /// fails, the preceding ones stay committed and the following ones are not sent.
+    Sequential,
+    /// [RemusMode::Atomic] if every transaction fits into a single Solana transaction,
+    /// [RemusMode::Sequential] otherwise.
+    AtomicOrSequential,
+}
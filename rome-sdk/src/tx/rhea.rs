@@ -25,4 +25,9 @@ impl<'a> RheaTx<'a> {
     pub fn from_ref(tx: &'a EthSignedTxTuple) -> Self {
         Self(Cow::Borrowed(tx))
     }
+
+    /// Returns the owned EthSignedTxTuple.
+    pub fn into_inner(self) -> EthSignedTxTuple {
+        self.0.into_owned()
+    }
 }
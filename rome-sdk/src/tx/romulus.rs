@@ -3,37 +3,78 @@ use std::borrow::Cow;
 use super::EthSignedTxTuple;
 use solana_sdk::instruction::Instruction;
 
+/// A step of a [RomulusTx]
+#[derive(Clone, Debug)]
+pub enum RomulusStep {
+    /// Ethereum transaction executed on its rollup
+    Evm(EthSignedTxTuple),
+    /// Solana instruction
+    Svm(Instruction),
+}
+
+impl From<EthSignedTxTuple> for RomulusStep {
+    fn from(tx: EthSignedTxTuple) -> Self {
+        Self::Evm(tx)
+    }
+}
+
+impl From<Instruction> for RomulusStep {
+    fn from(ix: Instruction) -> Self {
+        Self::Svm(ix)
+    }
+}
+
 /// Multiple Ethereum transactions and Solana instructions over multiple rollups
-/// executed atomically.
+/// executed atomically, in order.
 pub struct RomulusTx<'a> {
-    eth_txs: Cow<'a, [EthSignedTxTuple]>,
-    sol_ixs: Cow<'a, [Instruction]>,
+    steps: Cow<'a, [RomulusStep]>,
 }
 
 impl<'a> RomulusTx<'a> {
     /// Creates a new RomulusTx from separate lists of Ethereum transactions and Solana instructions.
+    /// The Ethereum transactions are executed first.
     pub fn new(eth_txs: Vec<EthSignedTxTuple>, sol_ixs: Vec<Instruction>) -> Self {
+        let steps = eth_txs
+            .into_iter()
+            .map(RomulusStep::Evm)
+            .chain(sol_ixs.into_iter().map(RomulusStep::Svm))
+            .collect::<Vec<_>>();
+
+        Self::from_steps(steps)
+    }
+
+    /// Creates a new RomulusTx from the ordered steps.
+    pub fn from_steps(steps: Vec<RomulusStep>) -> Self {
         Self {
-            eth_txs: Cow::Owned(eth_txs),
-            sol_ixs: Cow::Owned(sol_ixs),
+            steps: Cow::Owned(steps),
         }
     }
 
-    /// Creates a new RomulusTx from references to Ethereum transactions and Solana instructions.
-    pub fn from_ref(eth_txs: &'a [EthSignedTxTuple], sol_ixs: &'a [Instruction]) -> Self {
+    /// Creates a new RomulusTx from a reference to the ordered steps.
+    pub fn from_ref(steps: &'a [RomulusStep]) -> Self {
         Self {
-            eth_txs: Cow::Borrowed(eth_txs),
-            sol_ixs: Cow::Borrowed(sol_ixs),
+            steps: Cow::Borrowed(steps),
         }
     }
 
-    /// Get a reference to the Ethereum transactions.
-    pub fn eth_txs(&self) -> &[EthSignedTxTuple] {
-        &self.eth_txs
+    /// Get a reference to the steps, in order of execution.
+    pub fn steps(&self) -> &[RomulusStep] {
+        &self.steps
+    }
+
+    /// Iterate over the Ethereum transactions, in order of execution.
+    pub fn eth_txs(&self) -> impl Iterator<Item = &EthSignedTxTuple> {
+        self.steps.iter().filter_map(|step| match step {
+            RomulusStep::Evm(tx) => Some(tx),
+            RomulusStep::Svm(_) => None,
+        })
     }
 
-    /// Get a reference to the Solana instructions.
-    pub fn sol_ixs(&self) -> &[Instruction] {
-        &self.sol_ixs
+    /// Iterate over the Solana instructions, in order of execution.
+    pub fn sol_ixs(&self) -> impl Iterator<Item = &Instruction> {
+        self.steps.iter().filter_map(|step| match step {
+            RomulusStep::Evm(_) => None,
+            RomulusStep::Svm(ix) => Some(ix),
+        })
     }
 }
@@ -1,20 +1,27 @@
 use {
+    super::TxVersion,
+    crate::{
+        compute_units::{ComputeUsage, MAX_COMPUTE_UNIT_LIMIT, MAX_HEAP_FRAME},
+        signer::{sign_transaction, SharedSigner, TxSigner},
+    },
     solana_sdk::{
         address_lookup_table::AddressLookupTableAccount,
-        compute_budget::ComputeBudgetInstruction,
+        compute_budget::{self, ComputeBudgetInstruction},
         hash::Hash,
         instruction::Instruction,
         message::{v0, Message, VersionedMessage},
-        signature::Keypair,
-        signer::{Signer, SignerError},
+        pubkey::Pubkey,
+        signature::Signature,
+        signer::SignerError,
         transaction::{Transaction, VersionedTransaction},
     },
     std::borrow::Cow,
 };
 
-/// An atomic batch of instructions that can be composed into a single transaction
+/// An atomic batch of instructions that can be composed into a single transaction,
+/// with the compute resources measured by their emulation, if any
 #[derive(Clone)]
-pub struct AtomicIxBatch<'a>(Cow<'a, [Instruction]>);
+pub struct AtomicIxBatch<'a>(Cow<'a, [Instruction]>, Option<ComputeUsage>);
 
 /// [AtomicIxBatch] which owns the instructions with a static lifetime
 pub type OwnedAtomicIxBatch = AtomicIxBatch<'static>;
@@ -25,7 +32,7 @@ impl AtomicIxBatch<'static> {
     /// Create a new owned [IxBatch] from a list of [Instruction]s
     /// assuming batch size is less than or equal to the maximum transaction size
     pub fn new_owned(ixs: Vec<Instruction>) -> AtomicIxBatch<'static> {
-        AtomicIxBatch(Cow::Owned(ixs))
+        AtomicIxBatch(Cow::Owned(ixs), None)
     }
 
     /// Create multiple composible [IxBatch] from a list of [Instruction]s
@@ -34,11 +41,14 @@ impl AtomicIxBatch<'static> {
     }
     /// Add system instructions
     pub fn new_composible_owned(ix: Instruction) -> AtomicIxBatch<'static> {
-        AtomicIxBatch(Cow::Owned(vec![
-            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
-            ComputeBudgetInstruction::request_heap_frame(256 * 1024),
-            ix,
-        ]))
+        AtomicIxBatch(
+            Cow::Owned(vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNIT_LIMIT),
+                ComputeBudgetInstruction::request_heap_frame(MAX_HEAP_FRAME),
+                ix,
+            ]),
+            None,
+        )
     }
     
     /// Add svm instructions
@@ -48,54 +58,142 @@ impl AtomicIxBatch<'static> {
 }
 
 impl AtomicIxBatch<'_> {
+    /// Set the compute resources used by the batch, measured by the emulation
+    /// of its instructions
+    pub fn with_usage(mut self, usage: ComputeUsage) -> Self {
+        self.1 = Some(usage);
+        self
+    }
+
+    /// Compute resources used by the batch, `None` if they are not measured
+    pub fn usage(&self) -> Option<ComputeUsage> {
+        self.1
+    }
+
+    /// Copy of the batch starting with the `set_compute_unit_price` instruction.
+    /// The batch is copied unchanged if it already sets the price.
+    pub fn with_compute_unit_price(&self, micro_lamports: u64) -> AtomicIxBatch<'static> {
+        if self
+            .0
+            .iter()
+            .any(|ix| is_compute_budget_ix(ix, SET_COMPUTE_UNIT_PRICE_TAG))
+        {
+            return AtomicIxBatch(Cow::Owned(self.0.to_vec()), self.1);
+        }
+
+        let mut ixs = Vec::with_capacity(self.0.len() + 1);
+        ixs.push(ComputeBudgetInstruction::set_compute_unit_price(
+            micro_lamports,
+        ));
+        ixs.extend_from_slice(&self.0);
+
+        AtomicIxBatch(Cow::Owned(ixs), self.1)
+    }
+
+    /// Copy of the batch with the compute unit limit replaced by the given one
+    pub fn with_compute_unit_limit(&self, units: u32) -> AtomicIxBatch<'static> {
+        self.with_compute_budget_ix(
+            ComputeBudgetInstruction::set_compute_unit_limit(units),
+            SET_COMPUTE_UNIT_LIMIT_TAG,
+        )
+    }
+
+    /// Copy of the batch with the heap frame replaced by the given one, in bytes
+    pub fn with_heap_frame(&self, bytes: u32) -> AtomicIxBatch<'static> {
+        self.with_compute_budget_ix(
+            ComputeBudgetInstruction::request_heap_frame(bytes),
+            REQUEST_HEAP_FRAME_TAG,
+        )
+    }
+
+    // copy of the batch starting with the compute budget instruction,
+    // the instruction of the same kind is removed
+    fn with_compute_budget_ix(&self, budget_ix: Instruction, tag: u8) -> AtomicIxBatch<'static> {
+        let mut ixs = Vec::with_capacity(self.0.len() + 1);
+        ixs.push(budget_ix);
+        ixs.extend(
+            self.0
+                .iter()
+                .filter(|ix| !is_compute_budget_ix(ix, tag))
+                .cloned(),
+        );
+
+        AtomicIxBatch(Cow::Owned(ixs), self.1)
+    }
+
+    /// Compose an unsigned transaction, used for simulation.
+    /// The legacy transaction is composed if no lookup tables are given.
+    pub fn compose_unsigned_solana_tx(
+        &self,
+        payer: &Pubkey,
+        alt: &[AddressLookupTableAccount],
+    ) -> Result<VersionedTransaction, SignerError> {
+        if alt.is_empty() {
+            let message = Message::new(&self.0, Some(payer));
+            return Ok(Transaction::new_unsigned(message).into());
+        }
+
+        let message = v0::Message::try_compile(payer, &self.0, alt, Hash::default())
+            .map_err(|e| SignerError::Custom(e.to_string()))?;
+        let signatures =
+            vec![Signature::default(); message.header.num_required_signatures as usize];
+
+        Ok(VersionedTransaction {
+            signatures,
+            message: VersionedMessage::V0(message),
+        })
+    }
+
     /// # Safety
     ///
     /// Unsafely Create a new borrowed [IxBatch] from a list of [Instruction]s
     /// assuming batch size is less than or equal to the maximum transaction size
     pub unsafe fn new_borrowed(ixs: &[Instruction]) -> AtomicIxBatch {
-        AtomicIxBatch(Cow::Borrowed(ixs))
+        AtomicIxBatch(Cow::Borrowed(ixs), None)
     }
-    /// Compose a [Transaction] from [IxBatch]
-    pub fn compose_legacy_solana_tx(
+    /// Compose the message of a transaction of the given version
+    pub fn compose_message(
         &self,
-        payer: &Keypair,
+        payer: &Pubkey,
         blockhash: Hash,
-    ) -> VersionedTransaction {
-        let message = Message::new(&self.0, Some(&payer.pubkey()));
-
-        Transaction::new(&[payer], message, blockhash).into()
+        ver: &TxVersion,
+    ) -> Result<VersionedMessage, SignerError> {
+        match ver {
+            TxVersion::Legacy => Ok(VersionedMessage::Legacy(Message::new_with_blockhash(
+                &self.0,
+                Some(payer),
+                &blockhash,
+            ))),
+            TxVersion::V0(alt) => v0::Message::try_compile(payer, &self.0, alt, blockhash)
+                .map(VersionedMessage::V0)
+                .map_err(|e| SignerError::Custom(e.to_string())),
+        }
     }
 
-    pub fn compose_v0_solana_tx(
+    /// Compose a [VersionedTransaction] from [IxBatch] signed by the payer and the signers
+    pub async fn compose_solana_tx(
         &self,
-        payer: &Keypair,
+        payer: &dyn TxSigner,
+        signers: &[SharedSigner],
         blockhash: Hash,
-        alt: &[AddressLookupTableAccount],
+        ver: &TxVersion,
     ) -> Result<VersionedTransaction, SignerError> {
-        let message = v0::Message::try_compile(&payer.pubkey(), &self.0, alt, blockhash)
-            .map_err(|e| SignerError::Custom(e.to_string()))?;
+        let message = self.compose_message(&payer.pubkey(), blockhash, ver)?;
 
-        VersionedTransaction::try_new(VersionedMessage::V0(message), &[payer])
-    }
+        let mut all_signers = vec![payer];
+        all_signers.extend(signers.iter().map(|signer| signer.as_ref()));
 
-    /// Compose a [Transaction] from [IxBatch] and signers
-    pub fn compose_legacy_solana_tx_with_signers(
-        &self,
-        payer: &Keypair,
-        signers: &[&Keypair],
-        blockhash: Hash,
-    ) -> VersionedTransaction {
-        println!("compose_legacy_solana_tx_with_signers");
-        let message = Message::new(&self.0, Some(&payer.pubkey()));
+        sign_transaction(message, &all_signers).await
+    }
+}
 
-        let mut all_signers = vec![payer]; // Start with payer
-        all_signers.extend_from_slice(signers); // Add other signers
+// tags of the ComputeBudgetInstruction variants
+const REQUEST_HEAP_FRAME_TAG: u8 = 1;
+const SET_COMPUTE_UNIT_LIMIT_TAG: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE_TAG: u8 = 3;
 
-        all_signers.iter().for_each(|signer| {
-            println!("signer pubkey: {:?}", signer.pubkey());
-        });
-        Transaction::new(&all_signers, message, blockhash).into()
-    }
+fn is_compute_budget_ix(ix: &Instruction, tag: u8) -> bool {
+    ix.program_id == compute_budget::id() && ix.data.first() == Some(&tag)
 }
 
 impl std::ops::Deref for AtomicIxBatch<'_> {
@@ -1,7 +1,7 @@
 use {
     super::AtomicIxBatch,
-    solana_sdk::{address_lookup_table::AddressLookupTableAccount, signer::keypair::Keypair},
-    std::sync::Arc,
+    crate::signer::SharedSigner,
+    solana_sdk::{address_lookup_table::AddressLookupTableAccount, pubkey::Pubkey},
 };
 
 #[derive(Clone)]
@@ -9,12 +9,22 @@ pub enum TxVersion {
     Legacy,
     V0(Vec<AddressLookupTableAccount>),
 }
+
+impl TxVersion {
+    /// Address lookup tables of the transaction
+    pub fn alt(&self) -> &[AddressLookupTableAccount] {
+        match self {
+            TxVersion::Legacy => &[],
+            TxVersion::V0(alt) => alt,
+        }
+    }
+}
 // An execution step in a ix batch
 pub enum IxExecStepBatch<'a> {
     // Single step
     Single(AtomicIxBatch<'a>, TxVersion),
     // Single step with signers
-    SingleWithSigners(AtomicIxBatch<'a>, Vec<Arc<Keypair>>),
+    SingleWithSigners(AtomicIxBatch<'a>, Vec<SharedSigner>, TxVersion),
     // Parallel steps
     Parallel(Vec<AtomicIxBatch<'a>>, TxVersion),
     // Parallel steps with check tx state
@@ -27,12 +37,21 @@ pub enum IxExecStepBatch<'a> {
     End,
 }
 
+#[async_trait::async_trait]
 pub trait AdvanceTx<'a>: Send + Sync {
     type Error: std::fmt::Debug;
-    fn advance(&mut self) -> Result<IxExecStepBatch<'a>, Self::Error>;
-    fn advance_with_version(
+    async fn advance(&mut self) -> Result<IxExecStepBatch<'a>, Self::Error>;
+    async fn advance_with_version(
         &mut self,
         version: TxVersion,
     ) -> Result<IxExecStepBatch<'a>, Self::Error>;
-    fn payer(&self) -> Arc<Keypair>;
+    fn payer(&self) -> SharedSigner;
+    // address lookup table created by the transaction, if any
+    fn created_alt(&self) -> Option<Pubkey> {
+        None
+    }
+    // number of the execution steps left before the end, if known in advance
+    fn remaining_steps(&self) -> Option<usize> {
+        None
+    }
 }
@@ -0,0 +1,119 @@
+/// Max compute unit limit of a Solana transaction
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Max heap frame of a Solana transaction, in bytes
+pub const MAX_HEAP_FRAME: u32 = 256 * 1024;
+
+/// Heap frame of a Solana transaction which does not request one, in bytes
+pub const MIN_HEAP_FRAME: u32 = 32 * 1024;
+
+// the heap frame is requested in multiples of 1 KB
+const HEAP_FRAME_UNIT: u64 = 1024;
+
+/// Compute resources used by a transaction, measured by the emulation of its instructions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ComputeUsage {
+    /// Compute units consumed
+    pub units: u64,
+    /// Heap used, in bytes
+    pub heap: u64,
+}
+
+impl ComputeUsage {
+    /// Usage of a transaction executing the instructions of both transactions.
+    /// Every instruction is executed with its own heap.
+    pub fn join(self, other: ComputeUsage) -> ComputeUsage {
+        ComputeUsage {
+            units: self.units.saturating_add(other.units),
+            heap: self.heap.max(other.heap),
+        }
+    }
+}
+
+/// Policy of the compute unit limit of Solana transactions
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComputeUnitLimit {
+    /// Limit requested by the composer of the transaction
+    #[default]
+    Requested,
+    /// Units consumed by the emulation of the transaction plus the safety margin, in percents.
+    ///
+    /// Transactions without the emulated usage keep the requested limit. The margin should
+    /// cover the difference between the emulated and the actual state, e.g. for the iterations
+    /// of an iterative transaction sent in parallel.
+    Emulated(u32),
+}
+
+impl ComputeUnitLimit {
+    /// Compute unit limit of the transaction consumed the given number of units,
+    /// `None` to keep the requested limit
+    pub fn limit(&self, units_consumed: u64) -> Option<u32> {
+        match self {
+            ComputeUnitLimit::Requested => None,
+            ComputeUnitLimit::Emulated(margin) => {
+                let limit = with_margin(units_consumed, *margin);
+                Some(limit.min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32)
+            }
+        }
+    }
+}
+
+/// Policy of the heap frame of Solana transactions
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeapFrame {
+    /// Heap frame requested by the composer of the transaction
+    #[default]
+    Requested,
+    /// Heap used by the emulation of the transaction plus the safety margin, in percents.
+    ///
+    /// Transactions without the emulated usage keep the requested heap frame.
+    Emulated(u32),
+}
+
+impl HeapFrame {
+    /// Heap frame of the transaction used the given number of bytes,
+    /// `None` to keep the requested heap frame
+    pub fn frame(&self, heap_used: u64) -> Option<u32> {
+        match self {
+            HeapFrame::Requested => None,
+            HeapFrame::Emulated(margin) => {
+                let frame =
+                    with_margin(heap_used, *margin).div_ceil(HEAP_FRAME_UNIT) * HEAP_FRAME_UNIT;
+                Some(frame.clamp(MIN_HEAP_FRAME as u64, MAX_HEAP_FRAME as u64) as u32)
+            }
+        }
+    }
+}
+
+fn with_margin(value: u64, margin: u32) -> u64 {
+    value.saturating_mul(100 + margin as u64) / 100
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emulated_limit() {
+        assert_eq!(ComputeUnitLimit::Requested.limit(100_000), None);
+        assert_eq!(ComputeUnitLimit::Emulated(20).limit(100_000), Some(120_000));
+        assert_eq!(
+            ComputeUnitLimit::Emulated(20).limit(1_300_000),
+            Some(MAX_COMPUTE_UNIT_LIMIT)
+        );
+    }
+
+    #[test]
+    fn emulated_heap_frame() {
+        assert_eq!(HeapFrame::Requested.frame(100_000), None);
+        assert_eq!(HeapFrame::Emulated(10).frame(0), Some(MIN_HEAP_FRAME));
+        // 110_000 bytes rounded up to 108 KB
+        assert_eq!(HeapFrame::Emulated(10).frame(100_000), Some(108 * 1024));
+        assert_eq!(
+            HeapFrame::Emulated(10).frame(250 * 1024),
+            Some(MAX_HEAP_FRAME)
+        );
+    }
+}
@@ -1,13 +1,21 @@
 /// Batch of instructions
 pub mod batch;
+/// Compute unit limits of Solana transactions
+pub mod compute_units;
 /// Solana config
 pub mod config;
 /// Geyser interface
 pub mod geyser;
 /// Indexing strategies for data from solana node.
 pub mod indexers;
+/// Observers of transaction execution
+pub mod observer;
 /// Solana Payer
 pub mod payer;
+/// Priority fees of Solana transactions
+pub mod priority_fee;
+/// Signers of Solana transactions
+pub mod signer;
 /// Solana tower
 pub mod tower;
 /// Types related to solana.
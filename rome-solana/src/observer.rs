@@ -0,0 +1,108 @@
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Kind of an execution step of [AdvanceTx](crate::batch::AdvanceTx)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepKind {
+    /// Single Solana transaction
+    Single,
+    /// Single Solana transaction signed by additional signers
+    SingleWithSigners,
+    /// Solana transactions sent in parallel
+    Parallel,
+    /// Solana transactions sent in parallel, the results are checked by the next step
+    ParallelUnchecked,
+    /// Confirmation of the iterative transaction
+    Confirmation,
+    /// Waiting for the next slot
+    WaitNextSlot,
+}
+
+/// Lifecycle event of the execution of [AdvanceTx](crate::batch::AdvanceTx)
+#[derive(Clone, Debug)]
+pub enum TxEvent {
+    /// Execution step is started
+    StepStarted {
+        /// Index of the step
+        step: usize,
+        /// Kind of the step
+        kind: StepKind,
+        /// Number of Solana transactions sent by the step
+        txs: usize,
+    },
+    /// Solana transaction is sent
+    TxSent {
+        /// Signature of the transaction
+        signature: Signature,
+    },
+    /// Solana transaction is confirmed
+    TxConfirmed {
+        /// Signature of the transaction
+        signature: Signature,
+        /// Time from sending to confirmation
+        elapsed: Duration,
+    },
+    /// Iteration of an iterative transaction failed
+    IterationFailed {
+        /// Index of the step
+        step: usize,
+        /// Error of the iteration
+        error: String,
+    },
+    /// Address lookup table is created
+    AltCreated {
+        /// Address of the lookup table
+        address: Pubkey,
+    },
+    /// Execution waits for the slot following the given one
+    WaitNextSlot {
+        /// Slot to wait for
+        slot: u64,
+    },
+    /// Execution is completed
+    Done {
+        /// Signatures of all confirmed Solana transactions
+        signatures: Vec<Signature>,
+        /// Time of the whole execution
+        elapsed: Duration,
+    },
+    /// Execution failed
+    Failed {
+        /// Error of the execution
+        error: String,
+        /// Time of the whole execution
+        elapsed: Duration,
+    },
+}
+
+/// Observer of the execution of [AdvanceTx](crate::batch::AdvanceTx)
+///
+/// Called synchronously from the sending task, implementations must not block.
+pub trait TxObserver: Send + Sync {
+    /// Receives a lifecycle event
+    fn on_event(&self, event: &TxEvent);
+}
+
+/// Registered observers, shared by the clones of the owner
+#[derive(Clone, Default)]
+pub struct TxObservers(Arc<RwLock<Vec<Arc<dyn TxObserver>>>>);
+
+impl TxObservers {
+    /// Register an observer
+    pub fn register(&self, observer: Arc<dyn TxObserver>) {
+        self.0
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(observer);
+    }
+
+    /// Notify all observers
+    pub fn notify(&self, event: TxEvent) {
+        let observers = self.0.read().unwrap_or_else(|e| e.into_inner());
+
+        for observer in observers.iter() {
+            observer.on_event(&event);
+        }
+    }
+}
@@ -8,6 +8,7 @@ use anyhow::Context;
 use tokio::io::AsyncReadExt;
 
 /// A structure that represents the payer in the Solana network
+#[derive(Debug)]
 pub struct SolanaKeyPayer {
     pub payer: Keypair,
 }
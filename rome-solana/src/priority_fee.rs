@@ -0,0 +1,127 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_rpc_client_api::client_error::Result as ClientResult;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Max number of accounts accepted by `getRecentPrioritizationFees`
+const MAX_FEE_ACCOUNTS: usize = 128;
+
+/// Time the recent prioritization fees are reused for.
+/// The fees cover the last 150 slots, a few slots old fees are accurate enough.
+const RECENT_FEES_TTL: Duration = Duration::from_secs(2);
+
+/// Policy of the compute unit price (priority fee) of Solana transactions
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriorityFee {
+    /// Compute unit price is not set
+    #[default]
+    None,
+    /// Fixed compute unit price, in micro-lamports
+    Fixed(u64),
+    /// Percentile (0-100) of the recent prioritization fees paid
+    /// for the writable accounts of the transaction
+    Percentile(u8),
+    /// [PriorityFee::Percentile] limited by the maximum price, in micro-lamports
+    Capped {
+        /// Percentile (0-100) of the recent prioritization fees
+        percentile: u8,
+        /// Maximum compute unit price, in micro-lamports
+        max: u64,
+    },
+}
+
+impl PriorityFee {
+    /// Compute unit price of the transaction composed of the given instructions,
+    /// `None` if the price is not set
+    pub async fn compute_unit_price(
+        &self,
+        client: &RpcClient,
+        recent_fees: &RecentFees,
+        ixs: &[Instruction],
+    ) -> ClientResult<Option<u64>> {
+        let price = match self {
+            PriorityFee::None => return Ok(None),
+            PriorityFee::Fixed(price) => *price,
+            PriorityFee::Percentile(percentile) => {
+                fee_percentile(recent_fees.get(client, ixs).await?, *percentile)
+            }
+            PriorityFee::Capped { percentile, max } => {
+                fee_percentile(recent_fees.get(client, ixs).await?, *percentile).min(*max)
+            }
+        };
+
+        Ok(Some(price))
+    }
+}
+
+/// Cache of the recent prioritization fees by the writable accounts of the transactions,
+/// shared by the clones of the owner
+///
+/// The transactions of a step usually write the same accounts,
+/// the fees are requested once for all of them.
+#[derive(Clone, Default)]
+pub struct RecentFees(Arc<Mutex<HashMap<Vec<Pubkey>, (Instant, Vec<u64>)>>>);
+
+impl RecentFees {
+    /// Recent prioritization fees paid for the writable accounts of the instructions
+    pub async fn get(&self, client: &RpcClient, ixs: &[Instruction]) -> ClientResult<Vec<u64>> {
+        let accounts = ixs
+            .iter()
+            .flat_map(|ix| ix.accounts.iter())
+            .filter(|meta| meta.is_writable)
+            .map(|meta| meta.pubkey)
+            .collect::<BTreeSet<Pubkey>>()
+            .into_iter()
+            .take(MAX_FEE_ACCOUNTS)
+            .collect::<Vec<_>>();
+
+        // the lock is held during the request to not repeat it for the concurrent transactions
+        let mut cache = self.0.lock().await;
+        cache.retain(|_, (updated, _)| updated.elapsed() < RECENT_FEES_TTL);
+        if let Some((_, fees)) = cache.get(&accounts) {
+            return Ok(fees.clone());
+        }
+
+        let fees = client
+            .get_recent_prioritization_fees(&accounts)
+            .await?
+            .into_iter()
+            .map(|fee| fee.prioritization_fee)
+            .collect::<Vec<_>>();
+        cache.insert(accounts, (Instant::now(), fees.clone()));
+
+        Ok(fees)
+    }
+}
+
+// nearest-rank percentile of the fees, 0 if there are no fees
+fn fee_percentile(mut fees: Vec<u64>, percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+
+    fees.sort_unstable();
+    let index = (fees.len() - 1) * percentile.min(100) as usize / 100;
+
+    fees[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_of_fees() {
+        let fees = vec![50, 10, 40, 20, 30];
+
+        assert_eq!(fee_percentile(fees.clone(), 0), 10);
+        assert_eq!(fee_percentile(fees.clone(), 50), 30);
+        assert_eq!(fee_percentile(fees.clone(), 100), 50);
+        assert_eq!(fee_percentile(fees, 200), 50);
+        assert_eq!(fee_percentile(vec![], 75), 0);
+    }
+}
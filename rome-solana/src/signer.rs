@@ -0,0 +1,289 @@
+use crate::payer::SolanaKeyPayer;
+use anyhow::Context;
+use solana_sdk::{
+    message::VersionedMessage,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::{Signer, SignerError},
+    transaction::VersionedTransaction,
+};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Signer of Solana transactions: payers and additional signers
+#[async_trait::async_trait]
+pub trait TxSigner: Debug + Send + Sync {
+    /// Public key of the signer
+    fn pubkey(&self) -> Pubkey;
+
+    /// Sign the serialized message of a transaction
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError>;
+}
+
+/// [TxSigner] shared between transactions
+pub type SharedSigner = Arc<dyn TxSigner>;
+
+/// In-memory keypair
+#[async_trait::async_trait]
+impl TxSigner for Keypair {
+    fn pubkey(&self) -> Pubkey {
+        Signer::pubkey(self)
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        Signer::try_sign_message(self, message)
+    }
+}
+
+/// Keypair read from a JSON file
+#[async_trait::async_trait]
+impl TxSigner for SolanaKeyPayer {
+    fn pubkey(&self) -> Pubkey {
+        Signer::pubkey(&self.payer)
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        Signer::try_sign_message(&self.payer, message)
+    }
+}
+
+#[derive(serde::Serialize)]
+struct SignRequest {
+    pubkey: String,
+    message: String,
+}
+
+#[derive(serde::Deserialize)]
+struct SignResponse {
+    signature: String,
+}
+
+/// Configuration of a [RemoteSigner]
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct RemoteSignerConfig {
+    /// URL of the signing endpoint
+    pub url: url::Url,
+    /// Public key of the signer, base58
+    pub pubkey: String,
+    /// Bearer token of the signing endpoint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl Debug for RemoteSignerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteSignerConfig")
+            .field("url", &self.url)
+            .field("pubkey", &self.pubkey)
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+impl RemoteSignerConfig {
+    /// Create the [RemoteSigner]
+    pub fn signer(&self) -> anyhow::Result<RemoteSigner> {
+        let pubkey = self
+            .pubkey
+            .parse()
+            .context("Failed to parse remote signer pubkey")?;
+
+        match self.token.as_ref() {
+            Some(token) => RemoteSigner::new_with_auth(self.url.clone(), pubkey, token),
+            None => Ok(RemoteSigner::new(self.url.clone(), pubkey)),
+        }
+    }
+}
+
+/// Signer holding the private key outside of the process, e.g. in a KMS.
+///
+/// The message is sent as `POST <url>` with the JSON body
+/// `{"pubkey": "<base58>", "message": "<base64>"}`, the signer responds with
+/// `{"signature": "<base58>"}`. The signature is verified before it is used.
+#[derive(Debug)]
+pub struct RemoteSigner {
+    client: reqwest::Client,
+    url: url::Url,
+    pubkey: Pubkey,
+}
+
+impl RemoteSigner {
+    /// Create a new [RemoteSigner] of the given public key
+    pub fn new(url: url::Url, pubkey: Pubkey) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+            pubkey,
+        }
+    }
+
+    /// Create a new [RemoteSigner] authorized with a bearer token
+    pub fn new_with_auth(url: url::Url, pubkey: Pubkey, token: &str) -> anyhow::Result<Self> {
+        let mut auth = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))?;
+        auth.set_sensitive(true);
+
+        let client = reqwest::Client::builder()
+            .default_headers(std::iter::once((reqwest::header::AUTHORIZATION, auth)).collect())
+            .build()?;
+
+        Ok(Self {
+            client,
+            url,
+            pubkey,
+        })
+    }
+
+    async fn request_signature(&self, message: &[u8]) -> anyhow::Result<Signature> {
+        let req = SignRequest {
+            pubkey: self.pubkey.to_string(),
+            message: base64::encode(message),
+        };
+
+        let res: SignResponse = self
+            .client
+            .post(self.url.as_ref())
+            .json(&req)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(res.signature.parse()?)
+    }
+}
+
+#[async_trait::async_trait]
+impl TxSigner for RemoteSigner {
+    fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        let signature = self
+            .request_signature(message)
+            .await
+            .map_err(|e| SignerError::Connection(format!("{:#}", e)))?;
+
+        if !signature.verify(self.pubkey.as_ref(), message) {
+            return Err(SignerError::Custom(format!(
+                "Remote signer returned an invalid signature for {}",
+                self.pubkey
+            )));
+        }
+
+        Ok(signature)
+    }
+}
+
+/// Sign the message by the signers of the transaction.
+/// Every signer required by the message must be given, unused signers are rejected.
+pub async fn sign_transaction(
+    message: VersionedMessage,
+    signers: &[&dyn TxSigner],
+) -> Result<VersionedTransaction, SignerError> {
+    let required = message.header().num_required_signatures as usize;
+    let keys = &message.static_account_keys()[..required];
+
+    if signers
+        .iter()
+        .any(|signer| !keys.contains(&signer.pubkey()))
+    {
+        return Err(SignerError::KeypairPubkeyMismatch);
+    }
+
+    let data = message.serialize();
+    let mut signatures = Vec::with_capacity(required);
+    for key in keys {
+        let signer = signers
+            .iter()
+            .find(|signer| signer.pubkey() == *key)
+            .ok_or(SignerError::NotEnoughSigners)?;
+
+        signatures.push(signer.sign_message(&data).await?);
+    }
+
+    Ok(VersionedTransaction {
+        signatures,
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{hash::Hash, message::Message, system_instruction};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    // serves a single signing request with the keypair
+    async fn mock_signer(keypair: Keypair) -> url::Url {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/sign", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+
+            let mut buf = vec![];
+            let body = loop {
+                let mut chunk = [0; 1024];
+                let len = stream.read(&mut chunk).await.unwrap();
+                buf.extend_from_slice(&chunk[..len]);
+
+                let text = String::from_utf8_lossy(&buf);
+                if let Some((headers, body)) = text.split_once("\r\n\r\n") {
+                    let content_length = headers
+                        .lines()
+                        .find_map(|line| {
+                            line.to_lowercase()
+                                .strip_prefix("content-length: ")
+                                .map(str::to_string)
+                        })
+                        .unwrap()
+                        .parse::<usize>()
+                        .unwrap();
+                    if body.len() >= content_length {
+                        break body.to_string();
+                    }
+                }
+            };
+
+            let req: serde_json::Value = serde_json::from_str(&body).unwrap();
+            let message = base64::decode(req["message"].as_str().unwrap()).unwrap();
+            let signature = Signer::sign_message(&keypair, &message);
+
+            let body = serde_json::json!({ "signature": signature.to_string() }).to_string();
+            let res = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(res.as_bytes()).await.unwrap();
+        });
+
+        url.parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn sign_with_remote_signer() {
+        let payer = Keypair::new();
+        let cosigner = Keypair::new();
+        let remote = RemoteSigner::new(
+            mock_signer(cosigner.insecure_clone()).await,
+            Signer::pubkey(&cosigner),
+        );
+
+        let ix =
+            system_instruction::transfer(&Signer::pubkey(&cosigner), &Signer::pubkey(&payer), 1);
+        let message =
+            Message::new_with_blockhash(&[ix], Some(&Signer::pubkey(&payer)), &Hash::new_unique());
+
+        let signers: [&dyn TxSigner; 2] = [&payer, &remote];
+        let tx = sign_transaction(VersionedMessage::Legacy(message), &signers)
+            .await
+            .unwrap();
+
+        assert_eq!(tx.signatures.len(), 2);
+        assert!(tx.verify_with_results().into_iter().all(|ok| ok));
+    }
+}
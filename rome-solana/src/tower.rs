@@ -1,17 +1,16 @@
 use crate::batch::{AdvanceTx, AtomicIxBatch, IxExecStepBatch, TxVersion};
+use crate::compute_units::{ComputeUnitLimit, HeapFrame};
 use crate::indexers::clock::SolanaClock;
+use crate::observer::{StepKind, TxEvent, TxObserver, TxObservers};
+use crate::priority_fee::{PriorityFee, RecentFees};
+use crate::signer::{SharedSigner, TxSigner};
 use crate::types::AsyncAtomicRpcClient;
-use solana_client::{
-    nonblocking::rpc_client::RpcClient,
-    rpc_config::RpcSendTransactionConfig,
-};
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcSendTransactionConfig};
 use solana_rpc_client_api::client_error::Result as ClientResult;
-use solana_sdk::{
-    signature::{Keypair, Signature},
-    transaction::VersionedTransaction,
-};
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction};
+use std::borrow::Cow;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// A tower that manages functionalities of the Solana network
 #[derive(Clone)]
@@ -19,12 +18,69 @@ pub struct SolanaTower {
     client: AsyncAtomicRpcClient,
     /// Solana Clock
     clock: SolanaClock,
+    /// Observers of transaction execution
+    observers: TxObservers,
+    /// Priority fee policy
+    priority_fee: PriorityFee,
+    /// Recent prioritization fees used by the priority fee policy
+    recent_fees: RecentFees,
+    /// Compute unit limit policy
+    compute_unit_limit: ComputeUnitLimit,
+    /// Heap frame policy
+    heap_frame: HeapFrame,
 }
 
 impl SolanaTower {
     /// Create a new instance of [SolanaTower]
     pub fn new(client: AsyncAtomicRpcClient, clock: SolanaClock) -> Self {
-        Self { client, clock }
+        Self {
+            client,
+            clock,
+            observers: TxObservers::default(),
+            priority_fee: PriorityFee::default(),
+            recent_fees: RecentFees::default(),
+            compute_unit_limit: ComputeUnitLimit::default(),
+            heap_frame: HeapFrame::default(),
+        }
+    }
+
+    /// Set the priority fee policy of the transactions sent by the tower
+    pub fn with_priority_fee(mut self, priority_fee: PriorityFee) -> Self {
+        self.priority_fee = priority_fee;
+        self
+    }
+
+    /// Get the priority fee policy
+    pub fn priority_fee(&self) -> &PriorityFee {
+        &self.priority_fee
+    }
+
+    /// Set the compute unit limit policy of the transactions sent by the tower
+    pub fn with_compute_unit_limit(mut self, compute_unit_limit: ComputeUnitLimit) -> Self {
+        self.compute_unit_limit = compute_unit_limit;
+        self
+    }
+
+    /// Get the compute unit limit policy
+    pub fn compute_unit_limit(&self) -> &ComputeUnitLimit {
+        &self.compute_unit_limit
+    }
+
+    /// Set the heap frame policy of the transactions sent by the tower
+    pub fn with_heap_frame(mut self, heap_frame: HeapFrame) -> Self {
+        self.heap_frame = heap_frame;
+        self
+    }
+
+    /// Get the heap frame policy
+    pub fn heap_frame(&self) -> &HeapFrame {
+        &self.heap_frame
+    }
+
+    /// Register an observer of transaction execution.
+    /// The observer is shared by all clones of the tower.
+    pub fn register_observer(&self, observer: Arc<dyn TxObserver>) {
+        self.observers.register(observer);
     }
 
     /// Get the RPC client
@@ -45,17 +101,75 @@ impl SolanaTower {
     async fn to_tx<'a>(
         &self,
         ixs: &'a AtomicIxBatch<'a>,
-        payer: &Keypair,
+        payer: &dyn TxSigner,
+        signers: &[SharedSigner],
         ver: &TxVersion,
     ) -> ClientResult<VersionedTransaction> {
+        let ixs = self.with_priority_fee_ix(ixs).await?;
+        let ixs = self.with_compute_budget_ix(ixs);
         let blockhash = self.client.get_latest_blockhash().await?;
 
-        let tx = match ver {
-            TxVersion::Legacy => ixs.compose_legacy_solana_tx(payer, blockhash),
-            TxVersion::V0(alt) => ixs.compose_v0_solana_tx(payer, blockhash, alt)?,
+        Ok(ixs
+            .compose_solana_tx(payer, signers, blockhash, ver)
+            .await?)
+    }
+
+    /// Compose an unsigned transaction of [AtomicIxBatch], used for simulation.
+    ///
+    /// The priority fee and compute budget policies are applied
+    /// the same way as to the transactions sent by the tower.
+    pub async fn to_unsigned_tx(
+        &self,
+        ixs: &AtomicIxBatch<'_>,
+        payer: &Pubkey,
+    ) -> ClientResult<VersionedTransaction> {
+        let ixs = self.with_priority_fee_ix(ixs).await?;
+        let ixs = self.with_compute_budget_ix(ixs);
+
+        Ok(ixs.compose_unsigned_solana_tx(payer, &[])?)
+    }
+
+    // adds the compute unit price of the priority fee policy to the batch
+    async fn with_priority_fee_ix<'a>(
+        &self,
+        ixs: &'a AtomicIxBatch<'a>,
+    ) -> ClientResult<Cow<'a, AtomicIxBatch<'a>>> {
+        let price = self
+            .priority_fee
+            .compute_unit_price(&self.client, &self.recent_fees, ixs)
+            .await?;
+
+        Ok(match price {
+            Some(price) => Cow::Owned(ixs.with_compute_unit_price(price)),
+            None => Cow::Borrowed(ixs),
+        })
+    }
+
+    // sets the compute unit limit and the heap frame of the batch
+    // to the emulated usage plus the margins of the policies
+    fn with_compute_budget_ix<'a>(
+        &self,
+        ixs: Cow<'a, AtomicIxBatch<'a>>,
+    ) -> Cow<'a, AtomicIxBatch<'a>> {
+        let Some(usage) = ixs.usage() else {
+            return ixs;
+        };
+
+        let ixs = match self.compute_unit_limit.limit(usage.units) {
+            Some(limit) => {
+                tracing::debug!("Compute units consumed: {}, limit: {}", usage.units, limit);
+                Cow::Owned(ixs.with_compute_unit_limit(limit))
+            }
+            None => ixs,
         };
 
-        Ok(tx)
+        match self.heap_frame.frame(usage.heap) {
+            Some(frame) => {
+                tracing::debug!("Heap used: {}, frame: {}", usage.heap, frame);
+                Cow::Owned(ixs.with_heap_frame(frame))
+            }
+            None => ixs,
+        }
     }
 
     /// Send and confirm a transaction composed of [AtomicIxBatch]
@@ -63,13 +177,14 @@ impl SolanaTower {
     pub async fn send_and_confirm<'a>(
         &self,
         ixs: &'a AtomicIxBatch<'a>,
-        payer: &Keypair,
+        payer: &dyn TxSigner,
         ver: &TxVersion,
     ) -> ClientResult<Signature> {
-        let tx = self.to_tx(ixs, payer, ver).await?;
+        let tx = self.to_tx(ixs, payer, &[], ver).await?;
         tracing::info!("Sending tx: {:?}", tx.signatures[0]);
 
-        self.client.send_and_confirm_transaction_with_spinner_and_config(
+        let started = self.tx_sent(tx.signatures[0]);
+        let sig = self.client.send_and_confirm_transaction_with_spinner_and_config(
             &tx,
             self.client.commitment(),
             RpcSendTransactionConfig {
@@ -77,14 +192,29 @@ impl SolanaTower {
                 preflight_commitment: None,
                 ..RpcSendTransactionConfig::default()
             }
-        ).await
+        ).await?;
+        self.tx_confirmed(sig, started);
+
+        Ok(sig)
+    }
+
+    fn tx_sent(&self, signature: Signature) -> Instant {
+        self.observers.notify(TxEvent::TxSent { signature });
+        Instant::now()
+    }
+
+    fn tx_confirmed(&self, signature: Signature, started: Instant) {
+        self.observers.notify(TxEvent::TxConfirmed {
+            signature,
+            elapsed: started.elapsed(),
+        });
     }
 
     /// parallelize send and confirm transactions composed from multiple [AtomicIxBatch]
     pub async fn send_and_confirm_parallel<'a>(
         &self,
         batch: &'a [AtomicIxBatch<'a>],
-        payer: &Keypair,
+        payer: &dyn TxSigner,
         ver: &TxVersion,
     ) -> ClientResult<Vec<Signature>> {
         // create a batch of futures
@@ -102,7 +232,7 @@ impl SolanaTower {
     pub async fn send_and_confirm_parallel_unchecked<'a>(
         &self,
         batch: &'a [AtomicIxBatch<'a>],
-        payer: &Keypair,
+        payer: &dyn TxSigner,
         ver: &TxVersion,
     ) -> Vec<ClientResult<Signature>> {
         // create a batch of futures
@@ -117,18 +247,20 @@ impl SolanaTower {
     pub async fn send_and_confirm_with_signers<'a>(
         &self,
         ixs: &'a AtomicIxBatch<'a>,
-        payer: &Keypair,
-        signers: Vec<Arc<Keypair>>,
+        payer: &dyn TxSigner,
+        signers: Vec<SharedSigner>,
+        ver: &TxVersion,
     ) -> ClientResult<Signature> {
-        println!("send_and_confirm_with_signers");
-        let blockhash = self.client.get_latest_blockhash().await?;
-        let signers_slice: Vec<&Keypair> = signers.iter().map(|arc| arc.as_ref()).collect();
-
-        let tx = ixs.compose_legacy_solana_tx_with_signers(payer, &signers_slice, blockhash);
-        println!("Sending tx: {:?}", tx);
+        tracing::debug!("send_and_confirm_with_signers");
+        let tx = self.to_tx(ixs, payer, &signers, ver).await?;
+        tracing::debug!("Sending tx: {:?}", tx);
         tracing::info!("Sending tx: {:?}", tx.signatures[0]);
 
-        self.client.send_and_confirm_transaction(&tx).await
+        let started = self.tx_sent(tx.signatures[0]);
+        let sig = self.client.send_and_confirm_transaction(&tx).await?;
+        self.tx_confirmed(sig, started);
+
+        Ok(sig)
     }
 
     #[tracing::instrument(skip(self, tx))]
@@ -136,22 +268,56 @@ impl SolanaTower {
         &self,
         tx: &mut dyn AdvanceTx<'_, Error = Error>,
     ) -> anyhow::Result<Vec<Signature>> {
-        println!("send_and_confirm_tx_iterable\n");
+        tracing::debug!("send_and_confirm_tx_iterable");
+
+        let started = Instant::now();
+        let res = self.execute_tx_iterable(tx).await;
 
+        match &res {
+            Ok(signatures) => self.observers.notify(TxEvent::Done {
+                signatures: signatures.clone(),
+                elapsed: started.elapsed(),
+            }),
+            Err(e) => self.observers.notify(TxEvent::Failed {
+                error: format!("{:#}", e),
+                elapsed: started.elapsed(),
+            }),
+        }
+
+        res
+    }
+
+    async fn execute_tx_iterable<Error: std::fmt::Debug>(
+        &self,
+        tx: &mut dyn AdvanceTx<'_, Error = Error>,
+    ) -> anyhow::Result<Vec<Signature>> {
         let payer = tx.payer();
         let mut sigs = Vec::new();
         let mut unchecked_sigs = Vec::new();
+        let mut alt = None;
 
-        loop {
-            let tx = match tx.advance() {
-                Ok(tx) => tx,
+        for step in 0.. {
+            let batch = match tx.advance().await {
+                Ok(batch) => batch,
                 Err(e) => return Err(anyhow::anyhow!("Failed to advance tx: {:?}", e)),
             };
 
-            match tx {
+            if let Some(address) = tx.created_alt() {
+                if alt != Some(address) {
+                    alt = Some(address);
+                    self.observers.notify(TxEvent::AltCreated { address });
+                }
+            }
+
+            if let Some((kind, txs)) = step_kind(&batch) {
+                self.observers
+                    .notify(TxEvent::StepStarted { step, kind, txs });
+            }
+
+            match batch {
                 IxExecStepBatch::Single(tx, ver) => {
                     let sig = self
-                        .send_and_confirm(&tx, &payer, &ver)
+                        .send_and_confirm(&tx, payer.as_ref(), &ver)
                         .await
                         .map_err(|e| {
                             tracing::warn!("Failed to send and confirm single tx: {}", e);
@@ -162,10 +328,10 @@ impl SolanaTower {
 
                     sigs.push(sig);
                 }
-                IxExecStepBatch::SingleWithSigners(tx, signers) => {
-                    println!("SingleWithSigners");
+                IxExecStepBatch::SingleWithSigners(tx, signers, ver) => {
+                    tracing::debug!("SingleWithSigners");
                     let sig = self
-                        .send_and_confirm_with_signers(&tx, &payer, signers)
+                        .send_and_confirm_with_signers(&tx, payer.as_ref(), signers, &ver)
                         .await
                         .map_err(|e| {
                             tracing::warn!("Failed to send and confirm single tx: {}", e);
@@ -178,7 +344,7 @@ impl SolanaTower {
                 }
                 IxExecStepBatch::Parallel(batch, ver) => {
                     let batch_sigs = self
-                        .send_and_confirm_parallel(&batch, &payer, &ver)
+                        .send_and_confirm_parallel(&batch, payer.as_ref(), &ver)
                         .await
                         .map_err(|e| {
                             tracing::warn!("Failed to send and confirm txs in parallel: {}", e);
@@ -191,8 +357,15 @@ impl SolanaTower {
                 }
                 IxExecStepBatch::ParallelUnchecked(batch, ver) => {
                     unchecked_sigs = self
-                        .send_and_confirm_parallel_unchecked(&batch, &payer, &ver)
+                        .send_and_confirm_parallel_unchecked(&batch, payer.as_ref(), &ver)
                         .await;
+
+                    for e in unchecked_sigs.iter().filter_map(|res| res.as_ref().err()) {
+                        self.observers.notify(TxEvent::IterationFailed {
+                            step,
+                            error: e.to_string(),
+                        });
+                    }
                 }
                 IxExecStepBatch::ConfirmationIterativeTx(confirm) => {
                     if confirm {
@@ -225,14 +398,18 @@ impl SolanaTower {
                         return Err(anyhow::anyhow!("Failed to send iterative tx: {}", error));
                     }
                 }
-                IxExecStepBatch::WaitNextSlot(slot) => loop {
-                    if self.client.get_slot().await? > slot {
-                        break;
-                    } else {
-                        let ms = Duration::from_millis(100);
-                        tokio::time::sleep(ms).await;
+                IxExecStepBatch::WaitNextSlot(slot) => {
+                    self.observers.notify(TxEvent::WaitNextSlot { slot });
+
+                    loop {
+                        if self.client.get_slot().await? > slot {
+                            break;
+                        } else {
+                            let ms = Duration::from_millis(100);
+                            tokio::time::sleep(ms).await;
+                        }
                     }
-                },
+                }
                 IxExecStepBatch::End => break,
             }
         }
@@ -240,3 +417,148 @@ impl SolanaTower {
         Ok(sigs)
     }
 }
+
+// kind of the step and number of Solana transactions sent, None for the end of execution
+fn step_kind(batch: &IxExecStepBatch) -> Option<(StepKind, usize)> {
+    match batch {
+        IxExecStepBatch::Single(..) => Some((StepKind::Single, 1)),
+        IxExecStepBatch::SingleWithSigners(..) => Some((StepKind::SingleWithSigners, 1)),
+        IxExecStepBatch::Parallel(batch, _) => Some((StepKind::Parallel, batch.len())),
+        IxExecStepBatch::ParallelUnchecked(batch, _) => {
+            Some((StepKind::ParallelUnchecked, batch.len()))
+        }
+        IxExecStepBatch::ConfirmationIterativeTx(_) => Some((StepKind::Confirmation, 0)),
+        IxExecStepBatch::WaitNextSlot(_) => Some((StepKind::WaitNextSlot, 0)),
+        IxExecStepBatch::End => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::system_instruction;
+    use std::collections::VecDeque;
+    use std::sync::atomic::AtomicU64;
+    use std::sync::Mutex;
+
+    // replays the steps, the lookup table is created by the first step
+    struct MockTx {
+        payer: Arc<Keypair>,
+        steps: VecDeque<IxExecStepBatch<'static>>,
+        alt: Option<Pubkey>,
+    }
+
+    #[async_trait::async_trait]
+    impl AdvanceTx<'static> for MockTx {
+        type Error = String;
+
+        async fn advance(&mut self) -> Result<IxExecStepBatch<'static>, Self::Error> {
+            self.alt.get_or_insert_with(Pubkey::new_unique);
+            Ok(self.steps.pop_front().unwrap_or(IxExecStepBatch::End))
+        }
+
+        async fn advance_with_version(
+            &mut self,
+            _version: TxVersion,
+        ) -> Result<IxExecStepBatch<'static>, Self::Error> {
+            self.advance().await
+        }
+
+        fn payer(&self) -> SharedSigner {
+            self.payer.clone()
+        }
+
+        fn created_alt(&self) -> Option<Pubkey> {
+            self.alt
+        }
+    }
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<TxEvent>>);
+
+    impl TxObserver for Recorder {
+        fn on_event(&self, event: &TxEvent) {
+            self.0.lock().unwrap().push(event.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn observer_events_are_emitted_in_order() {
+        let client = Arc::new(RpcClient::new_mock("succeeds".to_string()));
+        let clock = SolanaClock {
+            slot: Arc::new(AtomicU64::new(0)),
+            blockhash: Arc::new(tokio::sync::RwLock::new(Hash::default())),
+        };
+        let tower = SolanaTower::new(client, clock);
+        let recorder = Arc::new(Recorder::default());
+        tower.register_observer(recorder.clone());
+
+        let payer = Arc::new(Keypair::new());
+        let transfer = || {
+            AtomicIxBatch::new_owned(vec![system_instruction::transfer(
+                &payer.pubkey(),
+                &Pubkey::new_unique(),
+                1,
+            )])
+        };
+        // requires the signature of an unknown signer, fails before sending
+        let unsigned = AtomicIxBatch::new_owned(vec![Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[],
+            vec![AccountMeta::new(Pubkey::new_unique(), true)],
+        )]);
+
+        let mut tx = MockTx {
+            payer: payer.clone(),
+            steps: VecDeque::from([
+                IxExecStepBatch::Single(transfer(), TxVersion::Legacy),
+                IxExecStepBatch::ParallelUnchecked(vec![transfer(), unsigned], TxVersion::Legacy),
+                IxExecStepBatch::ConfirmationIterativeTx(true),
+            ]),
+            alt: None,
+        };
+
+        let sigs = tower.send_and_confirm_tx_iterable(&mut tx).await.unwrap();
+        assert_eq!(sigs.len(), 2);
+
+        let events = recorder.0.lock().unwrap();
+        let mut events = events.iter();
+        let mut next = || events.next().expect("Missing event");
+
+        assert!(matches!(next(), TxEvent::AltCreated { .. }));
+        assert!(matches!(
+            next(),
+            TxEvent::StepStarted {
+                step: 0,
+                kind: StepKind::Single,
+                txs: 1
+            }
+        ));
+        assert!(matches!(next(), TxEvent::TxSent { signature } if *signature == sigs[0]));
+        assert!(matches!(next(), TxEvent::TxConfirmed { signature, .. } if *signature == sigs[0]));
+        assert!(matches!(
+            next(),
+            TxEvent::StepStarted {
+                step: 1,
+                kind: StepKind::ParallelUnchecked,
+                txs: 2
+            }
+        ));
+        assert!(matches!(next(), TxEvent::TxSent { signature } if *signature == sigs[1]));
+        assert!(matches!(next(), TxEvent::TxConfirmed { signature, .. } if *signature == sigs[1]));
+        assert!(matches!(next(), TxEvent::IterationFailed { step: 1, .. }));
+        assert!(matches!(
+            next(),
+            TxEvent::StepStarted {
+                step: 2,
+                kind: StepKind::Confirmation,
+                txs: 0
+            }
+        ));
+        assert!(matches!(next(), TxEvent::Done { signatures, .. } if *signatures == sigs));
+        assert!(events.next().is_none());
+    }
+}